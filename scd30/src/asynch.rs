@@ -0,0 +1,180 @@
+//! Async variant of the SCD30 driver, built on `embedded-hal-async`.
+//!
+//! Shares [`Command`](crate::Command), [`Sample`](crate::Sample), [`Error`](crate::Error)
+//! and the CRC logic in [`i2c`](crate::i2c) with the blocking driver in the crate root;
+//! only the I2C and delay traits, and the transaction plumbing, differ. Useful when a
+//! single executor should service the SCD30's clock-stretched, multi-second-interval
+//! reads alongside other async work (e.g. display or rise/set computation) instead of
+//! dedicating a thread to polling `GetDataReady`.
+
+use embedded_hal::i2c::SevenBitAddress;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::i2c::I2c;
+use std::time::Duration;
+
+use crate::{Command, Error, Sample, PRESSURE_RANGE_MBAR};
+
+/// Async handle to an SCD30 atmospheric sensor package.
+pub struct SCD30<I> {
+    comm: crate::i2c::I2cComm<I>,
+    continuous_enabled: bool,
+}
+
+impl<I> SCD30<I>
+where
+    I: I2c<SevenBitAddress>,
+{
+    /// Attach to an SCD30 on the bus and configure it as specified.
+    pub async fn new(bus: I, settings: crate::SCD30Settings) -> Result<Self, Error<I::Error>> {
+        let mut s = SCD30 {
+            comm: crate::i2c::I2cComm::new(bus, settings.retries),
+            continuous_enabled: false,
+        };
+        let period = settings.period.as_secs();
+        if !(2..=1800).contains(&period) {
+            return Err(Error::InvalidArgument(
+                "period must be between 2 and 1800 seconds",
+            ));
+        }
+
+        if let Some(mbar) = settings.pressure_mbar {
+            if mbar != 0 && !PRESSURE_RANGE_MBAR.contains(&mbar) {
+                return Err(Error::InvalidArgument(
+                    "pressure_mbar must be 0 (disabled) or between 700 and 1400 mBar",
+                ));
+            }
+        }
+
+        s.run_command(Command::SetContinuousInterval(period as u16))
+            .await?;
+        s.run_command(Command::StartContinuous(settings.pressure_mbar.unwrap_or(0)))
+            .await?;
+        s.continuous_enabled = true;
+
+        Ok(s)
+    }
+
+    /// Async equivalent of [`crate::SCD30::set_ambient_pressure`].
+    pub async fn set_ambient_pressure(&mut self, mbar: Option<u16>) -> Result<(), Error<I::Error>> {
+        if let Some(mbar) = mbar {
+            if mbar != 0 && !PRESSURE_RANGE_MBAR.contains(&mbar) {
+                return Err(Error::InvalidArgument(
+                    "pressure_mbar must be 0 (disabled) or between 700 and 1400 mBar",
+                ));
+            }
+        }
+        self.run_command(Command::StartContinuous(mbar.unwrap_or(0)))
+            .await
+    }
+
+    /// Async equivalent of [`crate::SCD30::set_automatic_self_calibration`].
+    pub async fn set_automatic_self_calibration(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), Error<I::Error>> {
+        self.run_command(Command::SetAutomaticSelfCalibration(enabled))
+            .await
+    }
+
+    /// Async equivalent of [`crate::SCD30::force_recalibration`].
+    pub async fn force_recalibration(&mut self, ppm: u16) -> Result<(), Error<I::Error>> {
+        if !(400..=2000).contains(&ppm) {
+            return Err(Error::InvalidArgument(
+                "forced recalibration value must be between 400 and 2000 ppm",
+            ));
+        }
+        self.run_command(Command::SetForcedRecalibration(ppm)).await
+    }
+
+    /// Async equivalent of [`crate::SCD30::set_temperature_offset`].
+    pub async fn set_temperature_offset(&mut self, centidegrees: u16) -> Result<(), Error<I::Error>> {
+        self.run_command(Command::SetTemperatureOffset(centidegrees))
+            .await
+    }
+
+    /// Async equivalent of [`crate::SCD30::set_altitude`].
+    pub async fn set_altitude(&mut self, meters: u16) -> Result<(), Error<I::Error>> {
+        self.run_command(Command::SetAltitude(meters)).await
+    }
+
+    /// Async equivalent of [`crate::SCD30::stop`].
+    pub async fn stop(mut self) -> Result<(), (Error<I::Error>, Self)> {
+        match self.run_command(Command::StopContinuous()).await {
+            Ok(_) => {
+                self.continuous_enabled = false;
+                Ok(())
+            }
+            Err(e) => Err((e, self)),
+        }
+    }
+
+    /// Async equivalent of [`crate::SCD30::sample`].
+    pub async fn sample(&mut self) -> Result<Sample, Error<I::Error>> {
+        const READY: Command = Command::GetDataReady();
+        let mut ready = [0u8; READY.data_bytes()];
+        self.run_command(READY).await?;
+        self.comm.read_async(&mut ready).await?;
+        if u16::from_be_bytes(ready) != 1 {
+            return Err(Error::NotReady());
+        }
+
+        const SAMPLE: Command = Command::ReadMeasurement();
+        let mut data = [0u8; SAMPLE.data_bytes()];
+        self.run_command(SAMPLE).await?;
+        self.comm.read_async(&mut data).await?;
+
+        // Interpret the sample.
+        // Per the datasheet (table 2), the order is "CO2", "temp", "humidity",
+        // each as a big-endian u32; we've already removed CRCs.
+        let mut co2_bytes = [0u8; 4];
+        let mut temp_bytes = [0u8; 4];
+        let mut humid_bytes = [0u8; 4];
+        co2_bytes.copy_from_slice(&data[0..4]);
+        temp_bytes.copy_from_slice(&data[4..8]);
+        humid_bytes.copy_from_slice(&data[8..12]);
+
+        Ok(Sample {
+            co2: f32::from_be_bytes(co2_bytes),
+            temperature: f32::from_be_bytes(temp_bytes),
+            humidity: f32::from_be_bytes(humid_bytes),
+        })
+    }
+
+    /// Async equivalent of [`crate::SCD30::firmware_version`].
+    pub async fn firmware_version(&mut self) -> Result<(u8, u8), Error<I::Error>> {
+        const VERSION: Command = Command::ReadFirmwareVersion();
+        let mut data = [0u8; VERSION.data_bytes()];
+        self.run_command(VERSION).await?;
+        self.comm.read_async(&mut data).await?;
+        Ok((data[0], data[1]))
+    }
+
+    /// Async equivalent of [`crate::SCD30::sample_blocking`]: awaits until a
+    /// measurement is available, polling [`Command::GetDataReady`] and sleeping
+    /// for [`crate::POLL_INTERVAL`] between attempts via the supplied `delay`,
+    /// bounded by `timeout`.
+    pub async fn sample_blocking<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout: Duration,
+    ) -> Result<Sample, Error<I::Error>> {
+        let mut waited = Duration::ZERO;
+        loop {
+            match self.sample().await {
+                Err(Error::NotReady()) => {
+                    if waited >= timeout {
+                        return Err(Error::NotReady());
+                    }
+                    delay.delay_ns(crate::POLL_INTERVAL.as_nanos() as u32).await;
+                    waited += crate::POLL_INTERVAL;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    /// Run a command, without getting any data back.
+    async fn run_command(&mut self, cmd: Command) -> Result<(), Error<I::Error>> {
+        self.comm.send_async(cmd.command(), cmd.data()).await
+    }
+}
@@ -7,20 +7,69 @@ use crc_any::CRCu8;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
 use std::fmt::Display;
 
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as AsyncI2c;
+
 /// I2c bus address of the SH30.
 const ADDRESS: u8 = 0x61;
 
+const WORD_SIZE: usize = size_of::<u16>();
+const WORD_WITH_CRC_SIZE: usize = size_of::<u16>() + size_of::<u8>();
+
+// The "read measurement" command reads back 6 u16s.
+const MAX_WORDS: usize = 6;
+
+// We have to locally allocate space for CRCs as well.
+const READ_BUFFER_SIZE: usize = MAX_WORDS * WORD_WITH_CRC_SIZE;
+
+/// Build the bytes to write for `command`/`data`, including the CRC if applicable.
+fn write_buffer(buffer: &mut [u8; 5], command: u16, data: Option<u16>) -> &[u8] {
+    buffer[0..=1].copy_from_slice(&command.to_be_bytes());
+    match data {
+        Some(data) => {
+            buffer[2..].copy_from_slice(&SH30CRC::new().add(data));
+            &buffer[..]
+        }
+        None => &buffer[..2],
+    }
+}
+
+/// Validate the CRCs of a just-read buffer and copy the payload (without CRCs) into `data`.
+fn validate_and_copy<E>(
+    data_with_crcs: &[u8],
+    data: &mut [u8],
+    words_requested: usize,
+) -> Result<(), Error<E>> {
+    let mut crc = SH30CRC::new();
+    let crc_err = data_with_crcs
+        .chunks(3)
+        .find_map(|chunk| crc.check(chunk).err());
+    if let Some(err) = crc_err {
+        return Err(Error::Crc(err));
+    }
+
+    for i in 0..words_requested {
+        let no_crc_offset = i * WORD_SIZE;
+        let with_crc_offset = i * WORD_WITH_CRC_SIZE;
+        data[no_crc_offset] = data_with_crcs[with_crc_offset];
+        data[no_crc_offset + 1] = data_with_crcs[with_crc_offset + 1];
+    }
+
+    Ok(())
+}
+
 /// Wrapper for the SH30's communication mechanism, including CRC-8 checking.
 pub(crate) struct I2cComm<I> {
     bus: I,
+    retries: u8,
 }
 
 impl<I> I2cComm<I>
 where
     I: I2c<SevenBitAddress>,
 {
-    pub fn new(bus: I) -> Self {
-        I2cComm { bus }
+    pub fn new(bus: I, retries: u8) -> Self {
+        I2cComm { bus, retries }
     }
 
     /// Sends the given command and associated data to the SH30 device.
@@ -30,62 +79,102 @@ where
         // - Command ID (2 bytes)
         // - Optional: 2 bytes of data + 1 byte of CRC.
         let mut buffer = [0u8; 5];
-        buffer[0..=1].copy_from_slice(&command.to_be_bytes());
-
-        let write_buf = match data {
-            Some(data) => {
-                buffer[2..].copy_from_slice(&SH30CRC::new().add(data));
-                &buffer[..]
-            }
-            None => &buffer[..2],
-        };
+        let write_buf = write_buffer(&mut buffer, command, data);
 
         self.bus.write(ADDRESS, write_buf).map_err(Error::I2cWrite)
     }
 
     /// Read data back from the device.
     /// Validates and removes CRCs.
+    ///
+    /// The I2C bus to the SCD30 is noisy and uses clock stretching, so transient
+    /// CRC faults are common; on a mismatch, the whole transaction (not just the
+    /// offending word) is re-issued up to `retries` times before `Error::Crc` is
+    /// surfaced.
     pub fn read(&mut self, data: &mut [u8]) -> Result<(), Error<I::Error>> {
-        const WORD_SIZE: usize = size_of::<u16>();
-        const WORD_WITH_CRC_SIZE: usize = size_of::<u16>() + size_of::<u8>();
-
-        // The "read measurement" command reads back 6 u16s.
-        const MAX_WORDS: usize = 6;
-
-        // We have to locally allocate space for CRCs as well.
-        const BUFFER_SIZE: usize = MAX_WORDS * WORD_WITH_CRC_SIZE;
+        let mut attempt = 0;
+        loop {
+            match self.read_once(data) {
+                Err(Error::Crc(err)) if attempt < self.retries => {
+                    attempt += 1;
+                    eprintln!(
+                        "invalid CRC (attempt {attempt}/{}): {:?}; retrying",
+                        self.retries, err
+                    );
+                }
+                result => return result,
+            }
+        }
+    }
 
+    /// A single, non-retrying attempt at [`I2cComm::read`].
+    fn read_once(&mut self, data: &mut [u8]) -> Result<(), Error<I::Error>> {
         // How many u16s do we need to read?
         let words_requested = (data.len() + 1) / 2;
         assert!(words_requested <= MAX_WORDS);
         assert!(words_requested >= 1);
 
-        let mut buffer = [0u8; BUFFER_SIZE];
-        let mut data_with_crcs = &mut buffer[..words_requested * WORD_WITH_CRC_SIZE];
+        let mut buffer = [0u8; READ_BUFFER_SIZE];
+        let data_with_crcs = &mut buffer[..words_requested * WORD_WITH_CRC_SIZE];
 
         self.bus
-            .read(ADDRESS, &mut data_with_crcs)
+            .read(ADDRESS, data_with_crcs)
             .map_err(Error::I2cRead)?;
 
-        let mut crc = SH30CRC::new();
-        // Check CRCs
-        let crc_err = data_with_crcs
-            .chunks(3)
-            .find_map(|chunk| crc.check(chunk).err());
-        if let Some(err) = crc_err {
-            eprintln!("invalid CRC: {:?}", err);
-            // return Err(Error::Crc(err));
-        }
+        validate_and_copy(data_with_crcs, data, words_requested)
+    }
+}
+
+/// Async equivalents of [`I2cComm`]'s communication methods, for use with
+/// [`embedded_hal_async`]'s `I2c` trait.
+#[cfg(feature = "async")]
+impl<I> I2cComm<I>
+where
+    I: AsyncI2c<SevenBitAddress>,
+{
+    /// Async equivalent of [`I2cComm::send`].
+    pub async fn send_async(&mut self, command: u16, data: Option<u16>) -> Result<(), Error<I::Error>> {
+        let mut buffer = [0u8; 5];
+        let write_buf = write_buffer(&mut buffer, command, data);
+
+        self.bus
+            .write(ADDRESS, write_buf)
+            .await
+            .map_err(Error::I2cWrite)
+    }
 
-        // CRCs are OK. Copy data.
-        for i in 0..words_requested {
-            let no_crc_offset = i * WORD_SIZE;
-            let with_crc_offset = i * WORD_WITH_CRC_SIZE;
-            data[no_crc_offset] = data_with_crcs[with_crc_offset];
-            data[no_crc_offset + 1] = data_with_crcs[with_crc_offset + 1];
+    /// Async equivalent of [`I2cComm::read`].
+    pub async fn read_async(&mut self, data: &mut [u8]) -> Result<(), Error<I::Error>> {
+        let mut attempt = 0;
+        loop {
+            match self.read_once_async(data).await {
+                Err(Error::Crc(err)) if attempt < self.retries => {
+                    attempt += 1;
+                    eprintln!(
+                        "invalid CRC (attempt {attempt}/{}): {:?}; retrying",
+                        self.retries, err
+                    );
+                }
+                result => return result,
+            }
         }
+    }
+
+    /// A single, non-retrying attempt at [`I2cComm::read_async`].
+    async fn read_once_async(&mut self, data: &mut [u8]) -> Result<(), Error<I::Error>> {
+        let words_requested = (data.len() + 1) / 2;
+        assert!(words_requested <= MAX_WORDS);
+        assert!(words_requested >= 1);
+
+        let mut buffer = [0u8; READ_BUFFER_SIZE];
+        let data_with_crcs = &mut buffer[..words_requested * WORD_WITH_CRC_SIZE];
+
+        self.bus
+            .read(ADDRESS, data_with_crcs)
+            .await
+            .map_err(Error::I2cRead)?;
 
-        Ok(())
+        validate_and_copy(data_with_crcs, data, words_requested)
     }
 }
 
@@ -1,10 +1,14 @@
 //! Driver for the SCD30 atmospheric sensor package.
 
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::{I2c, SevenBitAddress};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 mod i2c;
 
+#[cfg(feature = "async")]
+pub mod asynch;
+
 /// An error in communicating with the SCD30.
 pub enum Error<I2cError> {
     I2cWrite(I2cError),
@@ -69,16 +73,38 @@ pub struct SCD30Settings {
     /// Internal polling period of the SCD30.
     /// Defaults to 10 seconds; ranges from 2 to 1800 seconds.
     pub period: Duration,
+
+    /// Ambient pressure compensation, in mBar.
+    /// Must be in the range 700-1400, or `None` to disable pressure compensation.
+    ///
+    /// Note that pressure compensation and the configured-altitude setting
+    /// ([`SCD30::set_altitude`]) are mutually exclusive on this device:
+    /// setting one overrides the other.
+    pub pressure_mbar: Option<u16>,
+
+    /// Number of times to retry an I2C read transaction on a CRC mismatch
+    /// before surfacing `Error::Crc`. The bus to the SCD30 is noisy and uses
+    /// clock stretching, so transient CRC faults are common.
+    pub retries: u8,
 }
 
 impl Default for SCD30Settings {
     fn default() -> Self {
         Self {
             period: Duration::from_secs(10),
+            pressure_mbar: None,
+            retries: 3,
         }
     }
 }
 
+/// Valid range for ambient pressure compensation, in mBar.
+const PRESSURE_RANGE_MBAR: std::ops::RangeInclusive<u16> = 700..=1400;
+
+/// Interval between data-ready polls in [`SCD30::sample_blocking`].
+/// Well under the 2s minimum measurement period.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 impl<I> SCD30<I>
 where
     I: I2c<SevenBitAddress>,
@@ -86,7 +112,7 @@ where
     /// Attach to an SCD30 on the bus and configure it as specified.
     pub fn new(bus: I, settings: SCD30Settings) -> Result<Self, Error<I::Error>> {
         let mut s = SCD30 {
-            comm: i2c::I2cComm::new(bus),
+            comm: i2c::I2cComm::new(bus, settings.retries),
             continuous_enabled: false,
         };
         let period = settings.period.as_secs();
@@ -96,14 +122,83 @@ where
             ));
         }
 
+        if let Some(mbar) = settings.pressure_mbar {
+            if mbar != 0 && !PRESSURE_RANGE_MBAR.contains(&mbar) {
+                return Err(Error::InvalidArgument(
+                    "pressure_mbar must be 0 (disabled) or between 700 and 1400 mBar",
+                ));
+            }
+        }
+
         s.run_command(Command::SetContinuousInterval(period as u16))?;
-        // TODO: Support pressure adjustment?
-        s.run_command(Command::StartContinuous())?;
+        s.run_command(Command::StartContinuous(settings.pressure_mbar.unwrap_or(0)))?;
         s.continuous_enabled = true;
 
         Ok(s)
     }
 
+    /// Update the ambient pressure used for compensation, in mBar.
+    ///
+    /// Accepts a value in the range 700-1400, or `None` (equivalently 0) to disable
+    /// pressure compensation. Re-issues the start-continuous command with the new
+    /// value, since barometric pressure drifts over the course of a day.
+    ///
+    /// Note that pressure compensation and the configured-altitude setting
+    /// ([`SCD30::set_altitude`]) are mutually exclusive on this device: setting one
+    /// overrides the other.
+    pub fn set_ambient_pressure(&mut self, mbar: Option<u16>) -> Result<(), Error<I::Error>> {
+        if let Some(mbar) = mbar {
+            if mbar != 0 && !PRESSURE_RANGE_MBAR.contains(&mbar) {
+                return Err(Error::InvalidArgument(
+                    "pressure_mbar must be 0 (disabled) or between 700 and 1400 mBar",
+                ));
+            }
+        }
+        self.run_command(Command::StartContinuous(mbar.unwrap_or(0)))
+    }
+
+    /// Enable or disable automatic self-calibration (ASC).
+    ///
+    /// ASC requires several days of continuous operation with regular exposure
+    /// to fresh air (~400ppm CO2) to converge; enabling it on a sensor that
+    /// never sees fresh air will bias readings low.
+    pub fn set_automatic_self_calibration(&mut self, enabled: bool) -> Result<(), Error<I::Error>> {
+        self.run_command(Command::SetAutomaticSelfCalibration(enabled))
+    }
+
+    /// Force recalibration (FRC) of the sensor against a known CO2 concentration, in ppm.
+    ///
+    /// The sensor must already be running in continuous mode and have been
+    /// operating in a stable, known-CO2 environment for at least two minutes
+    /// before issuing this command, per the datasheet.
+    pub fn force_recalibration(&mut self, ppm: u16) -> Result<(), Error<I::Error>> {
+        if !(400..=2000).contains(&ppm) {
+            return Err(Error::InvalidArgument(
+                "forced recalibration value must be between 400 and 2000 ppm",
+            ));
+        }
+        self.run_command(Command::SetForcedRecalibration(ppm))
+    }
+
+    /// Set a temperature offset, in units of 0.01°C.
+    ///
+    /// Compensates for self-heating of the board the sensor is mounted on;
+    /// see the datasheet for the procedure to derive this value for a given
+    /// installation.
+    pub fn set_temperature_offset(&mut self, centidegrees: u16) -> Result<(), Error<I::Error>> {
+        self.run_command(Command::SetTemperatureOffset(centidegrees))
+    }
+
+    /// Set a configured altitude, in meters above sea level.
+    ///
+    /// This is an alternative to [`SCD30::set_ambient_pressure`] /
+    /// [`SCD30Settings::pressure_mbar`] for correcting the CO2 reading for
+    /// local air pressure; the two are mutually exclusive on this device, so
+    /// setting one overrides the other.
+    pub fn set_altitude(&mut self, meters: u16) -> Result<(), Error<I::Error>> {
+        self.run_command(Command::SetAltitude(meters))
+    }
+
     /// Stop continuous measurement.
     pub fn stop(mut self) -> Result<(), (Error<I::Error>, Self)> {
         match self.run_command(Command::StopContinuous()) {
@@ -149,6 +244,42 @@ where
         })
     }
 
+    /// Read the attached sensor's firmware version, as `(major, minor)`.
+    ///
+    /// Useful to log at startup and to gate workarounds on a known-buggy
+    /// firmware revision.
+    pub fn firmware_version(&mut self) -> Result<(u8, u8), Error<I::Error>> {
+        const VERSION: Command = Command::ReadFirmwareVersion();
+        let mut data = [0u8; VERSION.data_bytes()];
+        self.run_command(VERSION)?;
+        self.comm.read(&mut data)?;
+        Ok((data[0], data[1]))
+    }
+
+    /// Acquire a sample from the SCD30, blocking until one is available.
+    ///
+    /// Polls [`Command::GetDataReady`] and sleeps for [`POLL_INTERVAL`] between
+    /// attempts, using the caller-supplied `delay`, until a measurement appears
+    /// or `timeout` elapses, at which point this returns `Error::NotReady()`.
+    pub fn sample_blocking<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout: Duration,
+    ) -> Result<Sample, Error<I::Error>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.sample() {
+                Err(Error::NotReady()) => {
+                    if Instant::now() >= deadline {
+                        return Err(Error::NotReady());
+                    }
+                    delay.delay_ns(POLL_INTERVAL.as_nanos() as u32);
+                }
+                other => return other,
+            }
+        }
+    }
+
     /// Run a command, without getting any data back.
     fn run_command(&mut self, cmd: Command) -> Result<(), Error<I::Error>> {
         self.comm.send(cmd.command(), cmd.data())
@@ -176,11 +307,11 @@ impl core::fmt::Display for Sample {
     }
 }
 
-#[allow(dead_code)]
 enum Command {
     /// Start continous measurement.
-    /// TODO:Consider pressure adjustment?
-    StartContinuous(),
+    /// Argument is the ambient pressure in mBar (700-1400), or 0 to disable
+    /// pressure compensation.
+    StartContinuous(u16),
 
     /// Stop continuous measurement.
     StopContinuous(),
@@ -211,14 +342,18 @@ enum Command {
 
     /// Trigger a soft reset, forcing the sensor into its power-up state
     /// without clearing nonvolatile memory.
+    #[allow(dead_code)]
     SoftReset(),
+
+    /// Read the firmware version as a (major, minor) pair of bytes.
+    ReadFirmwareVersion(),
 }
 
 impl Command {
     /// Command identifier for this command.
     fn command(&self) -> u16 {
         match self {
-            Command::StartContinuous() => 0x0010,
+            Command::StartContinuous(_) => 0x0010,
             Command::StopContinuous() => 0x0104,
             Command::SetContinuousInterval(_) => 0x4600,
             Command::GetDataReady() => 0x0202,
@@ -228,13 +363,14 @@ impl Command {
             Command::SetTemperatureOffset(_) => 0x5403,
             Command::SetAltitude(_) => 0x5102,
             Command::SoftReset() => 0xD304,
+            Command::ReadFirmwareVersion() => 0xD100,
         }
     }
 
     /// Data to be sent with this command (if any).
     fn data(&self) -> Option<u16> {
         match self {
-            Command::StartContinuous() => Some(0x0000),
+            Command::StartContinuous(mbar) => Some(*mbar),
             Command::SetContinuousInterval(period) => Some(*period),
             Command::SetAutomaticSelfCalibration(false) => Some(0),
             Command::SetAutomaticSelfCalibration(true) => Some(1),
@@ -252,6 +388,7 @@ impl Command {
         let words = match self {
             Command::GetDataReady() => 1,
             Command::ReadMeasurement() => 6,
+            Command::ReadFirmwareVersion() => 1,
             _ => 0,
         };
 
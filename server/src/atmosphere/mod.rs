@@ -1,6 +1,7 @@
 //! Types for surfacing atmospheric data.
 
 use chrono::{DateTime, Utc};
+use std::time::Duration;
 
 /// A sample of local atmospheric conditions.
 ///
@@ -42,6 +43,60 @@ impl LastMeasurement {
     }
 }
 
+/// A fixed-size ring buffer of recent measurements of a single metric, sampled no
+/// more often than once per `interval`.
+///
+/// Used to render trend sparklines without needing unbounded storage for
+/// every reading that comes in.
+pub struct History<const N: usize> {
+    buf: [Option<f32>; N],
+    /// Index of the oldest stored sample (i.e. the next one to be overwritten).
+    head: usize,
+    last_push: DateTime<Utc>,
+    interval: Duration,
+}
+
+impl<const N: usize> History<N> {
+    /// Create an empty history that accepts at most one new sample per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        History {
+            buf: [None; N],
+            head: 0,
+            last_push: DateTime::<Utc>::from_timestamp(0, 0).expect("epoch is a valid timestamp"),
+            interval,
+        }
+    }
+
+    /// Record a new sample, if `interval` has elapsed since the last accepted one.
+    /// Does nothing if `value` is `None`.
+    pub fn push(&mut self, now: DateTime<Utc>, value: Option<f32>) {
+        let Some(value) = value else {
+            return;
+        };
+        if now.signed_duration_since(self.last_push).num_milliseconds()
+            < self.interval.as_millis() as i64
+        {
+            return;
+        }
+        self.last_push = now;
+        self.buf[self.head] = Some(value);
+        self.head = (self.head + 1) % N;
+    }
+
+    /// Iterate over the stored samples, oldest first. Slots that have never
+    /// been written (or were skipped due to `interval`) are `None` gaps.
+    pub fn iter(&self) -> impl Iterator<Item = Option<f32>> + '_ {
+        (0..N).map(move |i| self.buf[(self.head + i) % N])
+    }
+
+    /// The minimum and maximum of the present (non-`None`) samples.
+    pub fn min_max(&self) -> Option<(f32, f32)> {
+        let mut present = self.iter().flatten();
+        let first = present.next()?;
+        Some(present.fold((first, first), |(min, max), v| (min.min(v), max.max(v))))
+    }
+}
+
 /// A type that can get local atmospheric conditions.
 pub trait AtmosphereSampler {
     /// Get a current / latest sample of atmospheric conditions.
@@ -72,6 +127,20 @@ impl AtmosphereSampler for FakeAtmosphereSampler {
     }
 }
 
+/// National Weather Service-backed [`AtmosphereSampler`], for outdoor
+/// conditions when there's no local sensor.
+#[cfg(feature = "nws")]
+pub mod usgov;
+
+/// A sink for atmospheric readings: the write-side mirror of
+/// [`AtmosphereSampler`]. Implementations publish each new reading somewhere
+/// else (e.g. an MQTT broker), so that the clock's own sensor can feed other
+/// consumers.
+pub trait AtmospherePublisher {
+    /// Publish a freshly observed sample.
+    fn publish(&mut self, sample: &AtmosphereSample);
+}
+
 #[cfg(feature = "hardware")]
 mod scd30 {
     use chrono::Utc;
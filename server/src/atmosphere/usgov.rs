@@ -25,3 +25,209 @@
 //! API](https://developers.google.com/maps/documentation/air-quality/overview),
 //! with a price of $5/1000q (!)
 //!
+//! [`NwsAtmosphereSampler`] implements [`super::AtmosphereSampler`] on top of the
+//! three endpoints above: it resolves the nearest observation station once,
+//! then polls that station's latest observation on a background thread.
+
+use std::fmt::{self, Display};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::Deserialize;
+
+use super::{AtmosphereSample, AtmosphereSampler};
+
+/// Identifies this clock to the NWS API, per their API usage guidance.
+const USER_AGENT: &str = "aclock (https://github.com/cceckman/aclock)";
+
+/// How often to re-poll the station's latest observation.
+/// NWS stations typically report roughly once an hour; this is frequent
+/// enough to pick up a new reading promptly without hammering the API.
+const POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Errors encountered while talking to the NWS API.
+#[derive(Debug)]
+enum Error {
+    Http(ureq::Error),
+    Json(std::io::Error),
+    NoStations,
+}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "{e}"),
+            Error::Json(e) => write!(f, "{e}"),
+            Error::NoStations => write!(f, "no observation stations found nearby"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PointsResponse {
+    properties: PointsProperties,
+}
+
+#[derive(Deserialize)]
+struct PointsProperties {
+    #[serde(rename = "observationStations")]
+    observation_stations: String,
+}
+
+#[derive(Deserialize)]
+struct StationsResponse {
+    features: Vec<StationFeature>,
+}
+
+#[derive(Deserialize)]
+struct StationFeature {
+    properties: StationProperties,
+}
+
+#[derive(Deserialize)]
+struct StationProperties {
+    #[serde(rename = "stationIdentifier")]
+    station_identifier: String,
+}
+
+#[derive(Deserialize)]
+struct ObservationResponse {
+    properties: ObservationProperties,
+}
+
+#[derive(Deserialize)]
+struct ObservationProperties {
+    timestamp: chrono::DateTime<Utc>,
+    temperature: Measurement,
+    #[serde(rename = "relativeHumidity")]
+    relative_humidity: Measurement,
+}
+
+#[derive(Deserialize)]
+struct Measurement {
+    #[serde(rename = "unitCode")]
+    unit_code: String,
+    value: Option<f32>,
+}
+
+impl Measurement {
+    /// Convert to Celsius. Observations are documented as always
+    /// `wmoUnit:degC`, but fall back to a Fahrenheit conversion in case that
+    /// ever isn't true.
+    fn as_celsius(&self) -> Option<f32> {
+        let value = self.value?;
+        if self.unit_code.ends_with("degF") {
+            Some((value - 32.0) * 5.0 / 9.0)
+        } else {
+            Some(value)
+        }
+    }
+}
+
+/// Polls the US National Weather Service for outdoor conditions.
+///
+/// `sample()` is called synchronously, roughly once per second, so the actual
+/// HTTP polling happens on a background thread; `sample()` just reads back
+/// whatever that thread last cached. Until the first observation fetch
+/// completes, the cached sample is the default one (empty fields, epoch
+/// `timestamp`); after a failed fetch, it has empty fields and the time of
+/// that fetch as its `timestamp`, rather than stale data masquerading as
+/// fresh.
+pub struct NwsAtmosphereSampler {
+    latest: Arc<Mutex<AtmosphereSample>>,
+}
+
+impl NwsAtmosphereSampler {
+    /// Start polling the NWS station nearest to (`latitude`, `longitude`) on
+    /// a background thread.
+    pub fn new(latitude: f32, longitude: f32) -> Self {
+        let latest = Arc::new(Mutex::new(AtmosphereSample::default()));
+        let thread_latest = latest.clone();
+        thread::spawn(move || Self::poll_loop(latitude, longitude, thread_latest));
+        NwsAtmosphereSampler { latest }
+    }
+
+    fn poll_loop(latitude: f32, longitude: f32, latest: Arc<Mutex<AtmosphereSample>>) {
+        let station = loop {
+            match Self::resolve_station(latitude, longitude) {
+                Ok(station) => break station,
+                Err(e) => {
+                    tracing::warn!("failed to resolve nearest NWS station: {}", e);
+                    thread::sleep(POLL_INTERVAL);
+                }
+            }
+        };
+        tracing::info!("polling NWS station {} for outdoor conditions", station);
+
+        loop {
+            let sample = Self::fetch_observation(&station).unwrap_or_else(|e| {
+                tracing::warn!("failed to fetch NWS observation: {}", e);
+                AtmosphereSample {
+                    timestamp: Utc::now(),
+                    ..Default::default()
+                }
+            });
+            *latest.lock().expect("NWS sample mutex poisoned") = sample;
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Resolve the identifier of the observation station nearest to
+    /// (`latitude`, `longitude`), via `/points/{lat},{lon}` and the
+    /// `/gridpoints/.../stations` URL it returns.
+    fn resolve_station(latitude: f32, longitude: f32) -> Result<String, Error> {
+        let points_url = format!("https://api.weather.gov/points/{latitude},{longitude}");
+        let points: PointsResponse = ureq::get(&points_url)
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_json()?;
+
+        let stations: StationsResponse = ureq::get(&points.properties.observation_stations)
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_json()?;
+
+        stations
+            .features
+            .into_iter()
+            .next()
+            .map(|feature| feature.properties.station_identifier)
+            .ok_or(Error::NoStations)
+    }
+
+    /// Fetch the latest observation from `station`.
+    fn fetch_observation(station: &str) -> Result<AtmosphereSample, Error> {
+        let url = format!("https://api.weather.gov/stations/{station}/observations/latest");
+        let observation: ObservationResponse = ureq::get(&url)
+            .set("User-Agent", USER_AGENT)
+            .call()?
+            .into_json()?;
+
+        Ok(AtmosphereSample {
+            timestamp: observation.properties.timestamp,
+            temperature: observation.properties.temperature.as_celsius(),
+            relative_humidity: observation.properties.relative_humidity.value,
+            co2_ppm: None,
+        })
+    }
+}
+
+impl AtmosphereSampler for NwsAtmosphereSampler {
+    fn sample(&mut self) -> AtmosphereSample {
+        *self.latest.lock().expect("NWS sample mutex poisoned")
+    }
+}
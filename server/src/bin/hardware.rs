@@ -3,11 +3,9 @@ use std::time::Duration;
 use chrono::Local;
 use embedded_graphics::pixelcolor::Rgb888;
 use linux_embedded_hal::I2cdev;
-use server::{
-    atmosphere::{AtmosphereSampler, NullAtmosphereSampler},
-    context::Context,
-    Renderer, RendererSettings,
-};
+#[cfg(not(feature = "nws"))]
+use server::atmosphere::NullAtmosphereSampler;
+use server::{atmosphere::AtmosphereSampler, context::Context, Renderer, RendererSettings};
 
 fn get_i2c_atmosphere() -> Result<scd30::SCD30<I2cdev>, scd30::Error<linux_embedded_hal::I2CError>>
 {
@@ -18,12 +16,27 @@ fn get_i2c_atmosphere() -> Result<scd30::SCD30<I2cdev>, scd30::Error<linux_embed
     scd30::SCD30::new(device, scd30::SCD30Settings::default())
 }
 
-fn get_atmosphere() -> Box<dyn AtmosphereSampler> {
+/// Pick an atmosphere source: the local SCD30 if it's present, otherwise
+/// (when built with the `nws` feature) the nearest National Weather Service
+/// station for `settings`' location, so the twilight/temperature displays
+/// still have real outdoor data on a deployment with no CO2 sensor wired up.
+fn get_atmosphere(#[allow(unused_variables)] settings: &RendererSettings) -> Box<dyn AtmosphereSampler> {
     match get_i2c_atmosphere() {
         Ok(v) => Box::new(v),
         Err(e) => {
             tracing::error!("could not set up SCD30: {e}");
-            Box::new(NullAtmosphereSampler {})
+            #[cfg(feature = "nws")]
+            {
+                tracing::info!("falling back to NWS-backed outdoor readings");
+                Box::new(server::atmosphere::usgov::NwsAtmosphereSampler::new(
+                    settings.latitude,
+                    settings.longitude,
+                ))
+            }
+            #[cfg(not(feature = "nws"))]
+            {
+                Box::new(NullAtmosphereSampler {})
+            }
         }
     }
 }
@@ -51,7 +64,8 @@ fn main() {
     //
     // This observation brought to you by *strace*, your friend in understanding what mysterious
     // libraries are doing.
-    let mut atmo = get_atmosphere();
+    let settings = RendererSettings::default().with_color(Rgb888::new(255, 0, 0)); // Only the red channel actually shines through the wood
+    let mut atmo = get_atmosphere(&settings);
 
     #[cfg(feature = "simulator")]
     let mut displays = {
@@ -62,11 +76,8 @@ fn main() {
     #[cfg(not(feature = "simulator"))]
     let mut displays = server::led_displays::LedDisplays::new().unwrap();
 
-    let mut renderer: Renderer = RendererSettings::default()
-        .with_color(Rgb888::new(255, 0, 0)) // Only the red channel actually shines through the wood
-        .into();
+    let mut renderer: Renderer = settings.into();
 
-    // let mut atmo = NullAtmosphereSampler {};
     while !ctx.is_cancelled() {
         let t = Local::now();
         tracing::trace!("rendering clock at {}", t);
@@ -0,0 +1,113 @@
+//! HTTP server exposing the clock's rendered face as a PNG snapshot, or an
+//! MJPEG stream of them, for embedding in a dashboard or monitoring the
+//! clock remotely without the WASM canvas or a physical device.
+//!
+//! Reuses the same `Renderer::render` + `SimDisplays::screenshot` path as
+//! the day/year video binary (`bin/video.rs`), rendering "now" per request
+//! instead of a fixed time range.
+
+use std::time::Duration;
+
+use actix_web::{get, web, App, HttpResponse, HttpServer};
+use chrono::{FixedOffset, Utc};
+use futures_util::stream;
+use serde::Deserialize;
+use server::{atmosphere::NullAtmosphereSampler, simulator::SimDisplays, Renderer, RendererSettings};
+
+/// Query parameters shared by `/` and `/mjpeg`; all optional, falling back
+/// to [`RendererSettings::default`]'s location, UTC, and an 8x scale / 1fps
+/// rate.
+#[derive(Deserialize, Clone, Copy)]
+struct Params {
+    latitude: Option<f32>,
+    longitude: Option<f32>,
+    /// Timezone offset from UTC, in minutes (e.g. `-240` for EDT).
+    tz_offset_minutes: Option<i32>,
+    /// Integer upscale of the rendered image; same meaning as
+    /// `OutputSettingsBuilder::scale`.
+    scale: Option<u32>,
+    /// `/mjpeg` only: frames per second to stream.
+    fps: Option<f32>,
+}
+
+impl Params {
+    fn settings(&self) -> RendererSettings {
+        let mut settings = RendererSettings::default();
+        if let Some(latitude) = self.latitude {
+            settings.latitude = latitude;
+        }
+        if let Some(longitude) = self.longitude {
+            settings.longitude = longitude;
+        }
+        settings
+    }
+
+    fn now(&self) -> chrono::DateTime<FixedOffset> {
+        let offset_minutes = self.tz_offset_minutes.unwrap_or(0).clamp(-1440, 1440);
+        let offset_seconds = offset_minutes * 60;
+        let tz = FixedOffset::east_opt(offset_seconds).unwrap_or(FixedOffset::east_opt(0).unwrap());
+        Utc::now().with_timezone(&tz)
+    }
+
+    fn scale(&self) -> u32 {
+        self.scale.unwrap_or(8).clamp(1, 64)
+    }
+}
+
+/// Render one frame for "now" and encode it as PNG bytes.
+fn render_png(params: Params) -> Vec<u8> {
+    let mut displays = SimDisplays::new_hidden();
+    let mut renderer: Renderer = params.settings().into();
+    let mut atmosphere = NullAtmosphereSampler {};
+    renderer.render(&mut displays, &mut atmosphere, params.now());
+
+    let image = displays.screenshot(params.scale());
+    let file = tempfile::NamedTempFile::with_suffix(".png").expect("could not create temp file");
+    image.save_png(file.path()).expect("could not encode png");
+    std::fs::read(file.path()).expect("could not read encoded png")
+}
+
+#[get("/")]
+async fn snapshot(params: web::Query<Params>) -> HttpResponse {
+    let params = *params;
+    let bytes = web::block(move || render_png(params))
+        .await
+        .expect("render task panicked");
+    HttpResponse::Ok().content_type("image/png").body(bytes)
+}
+
+#[get("/mjpeg")]
+async fn mjpeg(params: web::Query<Params>) -> HttpResponse {
+    const BOUNDARY: &str = "aclockframe";
+    let fps = params.fps.unwrap_or(1.0).clamp(0.1, 30.0);
+    let interval = Duration::from_secs_f32(1.0 / fps);
+
+    let body = stream::unfold(*params, move |params| async move {
+        let frame = web::block(move || render_png(params)).await.ok()?;
+        actix_web::rt::time::sleep(interval).await;
+
+        let mut part = format!(
+            "--{BOUNDARY}\r\nContent-Type: image/png\r\nContent-Length: {}\r\n\r\n",
+            frame.len()
+        )
+        .into_bytes();
+        part.extend_from_slice(&frame);
+        part.extend_from_slice(b"\r\n");
+        Some((Ok::<_, actix_web::Error>(web::Bytes::from(part)), params))
+    });
+
+    HttpResponse::Ok()
+        .content_type(format!("multipart/x-mixed-replace; boundary={BOUNDARY}"))
+        .streaming(body)
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt::init();
+    let addr = ("0.0.0.0", 8080);
+    tracing::info!("listening on {}:{}", addr.0, addr.1);
+    HttpServer::new(|| App::new().service(snapshot).service(mjpeg))
+        .bind(addr)?
+        .run()
+        .await
+}
@@ -35,7 +35,7 @@ fn make_samples(
             break;
         }
         renderer.render(&mut displays, t);
-        let buffer = displays.screenshot();
+        let buffer = displays.screenshot(20);
 
         let path = outdir.join(format!("{i:04}.png"));
         buffer.save_png(&path).unwrap();
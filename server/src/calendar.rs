@@ -0,0 +1,121 @@
+//! Calendars the face's date line can render, selected by
+//! [`RendererSettings::calendar`](crate::RendererSettings::calendar) so
+//! [`crate::face::DateView`] doesn't need to know the layout details of any
+//! one of them.
+
+use chrono::{Datelike, NaiveDate};
+
+/// Which calendar system to render the date in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Calendar {
+    /// The everyday Gregorian calendar, as `DDMONYY`.
+    #[default]
+    Gregorian,
+    /// The International Fixed Calendar: 13 28-day months, plus a Year Day
+    /// (and in leap years, a Leap Day) that belong to no month.
+    InternationalFixed,
+}
+
+/// 3-character English month abbreviations, Gregorian calendar.
+fn month_en3(number: u32) -> &'static str {
+    match number {
+        1 => "JAN",
+        2 => "FEB",
+        3 => "MAR",
+        4 => "APR",
+        5 => "MAY",
+        6 => "JUN",
+        7 => "JUL",
+        8 => "AUG",
+        9 => "SEP",
+        10 => "OCT",
+        11 => "NOV",
+        12 => "DEC",
+        _ => "???",
+    }
+}
+
+/// 3-character month abbreviations for the International Fixed Calendar's 13
+/// months, "Sol" being the traditional name for the extra month.
+const IFC_MONTHS: [&str; 13] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "SOL", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+impl Calendar {
+    /// Render `date` in this calendar as a short (at most 7-character)
+    /// string, fitting the same 4x6 date line the Gregorian format uses.
+    pub fn format(&self, date: NaiveDate) -> String {
+        match self {
+            Calendar::Gregorian => format!(
+                "{:02}{}{:02}",
+                date.day(),
+                month_en3(date.month()),
+                date.year() % 100
+            ),
+            Calendar::InternationalFixed => format_ifc(date),
+        }
+    }
+}
+
+/// Render `date` per the International Fixed Calendar: ordinal day `0..364`
+/// maps onto 13 28-day months (`ordinal0/28 + 1`, `ordinal0 % 28 + 1`); the
+/// last day of the year is the intercalary Year Day, and in leap years the
+/// intercalary Leap Day falls right after the sixth month (June 28),
+/// pushing Sol and every later month back by one day. Neither intercalary
+/// day belongs to a month or week.
+fn format_ifc(date: NaiveDate) -> String {
+    let year = date.year();
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let ordinal0 = date.ordinal0() as i64;
+    let last_ordinal0 = if leap_year { 365 } else { 364 };
+
+    if ordinal0 == last_ordinal0 {
+        return "YEARDAY".to_string();
+    }
+    if leap_year && ordinal0 == 168 {
+        return "LEAPDAY".to_string();
+    }
+
+    // Leap Day occupies ordinal0 168 in leap years, so every day after it
+    // shifts back by one before dividing into months.
+    let month_ordinal = if leap_year && ordinal0 > 168 {
+        ordinal0 - 1
+    } else {
+        ordinal0
+    };
+
+    let month = (month_ordinal / 28) as usize;
+    let day = month_ordinal % 28 + 1;
+    format!("{day:02}{}{:02}", IFC_MONTHS[month], year % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ifc_known_dates_non_leap_year() {
+        let cases = [
+            (NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(), "01JAN23"),
+            (NaiveDate::from_ymd_opt(2023, 1, 28).unwrap(), "28JAN23"),
+            (NaiveDate::from_ymd_opt(2023, 6, 18).unwrap(), "01SOL23"),
+            (NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(), "YEARDAY"),
+        ];
+        for (date, want) in cases {
+            assert_eq!(format_ifc(date), want, "{date}");
+        }
+    }
+
+    #[test]
+    fn ifc_known_dates_leap_year() {
+        let cases = [
+            (NaiveDate::from_ymd_opt(2024, 6, 17).unwrap(), "LEAPDAY"),
+            (NaiveDate::from_ymd_opt(2024, 6, 18).unwrap(), "01SOL24"),
+            (NaiveDate::from_ymd_opt(2024, 12, 30).unwrap(), "28DEC24"),
+            (NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(), "YEARDAY"),
+        ];
+        for (date, want) in cases {
+            assert_eq!(format_ifc(date), want, "{date}");
+        }
+    }
+}
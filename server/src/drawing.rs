@@ -1,20 +1,16 @@
 //! Utilities for drawing.
 
-use std::fmt::Debug;
-
 use embedded_graphics::{
-    pixelcolor::Rgb888,
-    prelude::{DrawTarget, Point, Size},
+    pixelcolor::{Rgb888, RgbColor},
+    prelude::{Point, Size},
     Pixel,
 };
 
-use embedded_graphics_core::prelude::OriginDimensions;
-
-use crate::NeoPixelColor;
+use crate::{raster::Canvas, NeoPixelColor};
 
 /// Enumerates the points along the perimeter, from 6 o'clock to 6 o'clock, clockwise.
 /// Note: This is an infinite iterator.
-struct PerimiterTracer {
+pub(crate) struct PerimiterTracer {
     next: Point,
     bounds: Size,
 }
@@ -70,23 +66,199 @@ impl Iterator for PerimiterTracer {
     }
 }
 
-/// Draw an edge onto the display,
-/// assuming the display has a 1px border representing the edge.
-#[allow(unused)]
-pub fn render_edge<D>(edge: &[NeoPixelColor], display: &mut D)
-where
-    D: DrawTarget<Color = Rgb888> + OriginDimensions,
-    D::Error: Debug,
+/// Rasterize the edge ring as analytically anti-aliased arc wedges into
+/// `canvas`, one wedge per LED in `edge`, sharing `crate::raster`'s coverage
+/// math with [`render_matrix_dots`] so both backends' LEDs get the same
+/// soft-edged look. `center`/`inner_radius`/`outer_radius` describe the
+/// ring's geometry in `canvas`'s own pixel space, letting callers with very
+/// different scales ([`crate::simulator::SimDisplays`]'s 1px border versus
+/// [`crate::web::WebDisplays`]'s much larger canvas) share this one
+/// implementation.
+///
+/// Edge LEDs are ordered from 6 o'clock, clockwise, matching
+/// [`PerimiterTracer`]; [`crate::raster::wedge_coverage`] measures its
+/// angles from 3 o'clock, hence the quarter-turn offset below.
+pub fn render_edge_aa(
+    edge: &[NeoPixelColor],
+    canvas: &mut Canvas,
+    center: (f32, f32),
+    inner_radius: f32,
+    outer_radius: f32,
+) {
+    if edge.is_empty() {
+        return;
+    }
+    let wedge_angle = std::f32::consts::TAU / edge.len() as f32;
+    let start_offset = std::f32::consts::FRAC_PI_2;
+    for (i, color) in edge.iter().enumerate() {
+        let start = start_offset + i as f32 * wedge_angle;
+        let end = start + wedge_angle;
+        canvas.fill_wedge(
+            center,
+            inner_radius,
+            outer_radius,
+            start,
+            end,
+            rgbw_to_rgb(*color),
+        );
+    }
+}
+
+/// Rasterize a frame of matrix content as round, anti-aliased LED dots into
+/// `canvas`: one dot of `radius` pixels per non-black pixel in `pixels` (the
+/// face being conceptually a grid of LEDs that are either lit or dark, a
+/// dark one doesn't need compositing), spaced `cell_size` canvas pixels
+/// apart and offset by `offset` from `canvas`'s origin. Mirrors what
+/// [`crate::web::WebDisplays`] has always done for the web preview, so
+/// [`crate::simulator::SimDisplays`] gets the same "round LED" look instead
+/// of square pixels.
+pub fn render_matrix_dots<I>(
+    pixels: I,
+    canvas: &mut Canvas,
+    offset: Point,
+    cell_size: f32,
+    radius: f32,
+) where
+    I: IntoIterator<Item = Pixel<Rgb888>>,
 {
-    let points = PerimiterTracer::new(display.size()).take(edge.len());
-    let edge_pixels = edge
-        .iter()
-        .map(|color| {
-            let [r, g, b, _w] = *color;
-            // TODO: Incorporate W channel
-            Rgb888::new(r, g, b)
-        })
-        .zip(points)
-        .map(|(c, p)| Pixel(p, c));
-    display.draw_iter(edge_pixels).expect("infallible");
+    for Pixel(p, color) in pixels {
+        if color == Rgb888::BLACK {
+            continue;
+        }
+        let center = (
+            offset.x as f32 + (p.x as f32 + 0.5) * cell_size,
+            offset.y as f32 + (p.y as f32 + 0.5) * cell_size,
+        );
+        canvas.fill_dot(center, radius, color);
+    }
+}
+
+/// Build an RGBW NeoPixel value for a given overall brightness (0.0-1.0)
+/// during daylight hours. The dedicated white LED (`w`) and the red channel
+/// are both driven at `brightness`; green and blue are driven at the same
+/// level less what `warmth` (0.0 = neutral white, 1.0 = warm/incandescent)
+/// pulls out of them (up to 20% of green and 60% of blue). The RGB channels
+/// are not scaled down to a tint, so they emit about as much as `w` does.
+pub fn daylight_rgbw(brightness: f32, warmth: f32) -> NeoPixelColor {
+    let brightness = brightness.clamp(0.0, 1.0);
+    let warmth = warmth.clamp(0.0, 1.0);
+    let w = (brightness * 255.0) as u8;
+    let r = w;
+    let g = (brightness * (1.0 - 0.2 * warmth) * 255.0) as u8;
+    let b = (brightness * (1.0 - 0.6 * warmth) * 255.0) as u8;
+    [r, g, b, w]
+}
+
+/// Build an RGBW NeoPixel value for a given overall brightness (0.0-1.0)
+/// during night hours. `white_balance` (0.0 = pure blue nightlight, 1.0 =
+/// neutral white) blends between the two: a mostly-blue glow is easier on
+/// the eyes and mimics moonlight, but some installs may prefer plain white.
+pub fn night_rgbw(brightness: f32, white_balance: f32) -> NeoPixelColor {
+    let brightness = brightness.clamp(0.0, 1.0);
+    let white_balance = white_balance.clamp(0.0, 1.0);
+    let b = (brightness * (1.0 - white_balance) * 255.0) as u8;
+    let w = (brightness * white_balance * 255.0) as u8;
+    [0, 0, b, w]
+}
+
+/// Linearly blend two RGBW NeoPixel values, channel by channel. `t = 0.0`
+/// returns `a`, `t = 1.0` returns `b`; values outside `0.0..=1.0` extrapolate.
+pub fn blend(a: NeoPixelColor, b: NeoPixelColor, t: f32) -> NeoPixelColor {
+    std::array::from_fn(|i| (a[i] as f32 + (b[i] as f32 - a[i] as f32) * t).clamp(0.0, 255.0) as u8)
+}
+
+/// How strongly the moon dial's lit pixels blend `phase_color` over the
+/// sun-driven glow already there.
+const MOON_LIT_ALPHA: f32 = 0.9;
+
+/// How strongly the moon dial's unlit pixels darken the glow already there;
+/// low enough that the dial still reads "alongside" it rather than punching
+/// a solid hole through it.
+const MOON_UNLIT_ALPHA: f32 = 0.25;
+
+/// Paint the moon's illuminated fraction onto a small contiguous arc of the
+/// edge, centered on the point [`PerimiterTracer`] places nearest 12 o'clock.
+/// `arc_pixels` of the edge are touched, `illuminated_fraction` of those lit
+/// with `phase_color`, blended over whatever's already there (not
+/// overwritten), giving a live lunar dial alongside the sun-driven glow over
+/// the rest of the strip.
+pub fn render_moon(
+    edge: &mut [NeoPixelColor],
+    arc_pixels: usize,
+    illuminated_fraction: f32,
+    phase_color: NeoPixelColor,
+) {
+    if edge.is_empty() || arc_pixels == 0 {
+        return;
+    }
+
+    // PerimiterTracer only needs a roughly edge-length-sized rectangle to
+    // find a reasonable "12 o'clock" index; the real edge strip need not be
+    // arranged as an actual rectangle's perimeter.
+    let side = (edge.len() as u32 / 4).max(1);
+    let bounds = Size::new(side, side);
+    let top_center = Point::new((bounds.width / 2) as i32, 0);
+
+    let noon_index = PerimiterTracer::new(bounds)
+        .take(edge.len())
+        .enumerate()
+        .min_by_key(|(_, p)| (p.x - top_center.x).pow(2) + (p.y - top_center.y).pow(2))
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+
+    let half = arc_pixels / 2;
+    let start = noon_index.saturating_sub(half);
+    let lit = (illuminated_fraction.clamp(0.0, 1.0) * arc_pixels as f32).round() as usize;
+
+    for offset in 0..arc_pixels {
+        let Some(idx) = start.checked_add(offset).filter(|i| *i < edge.len()) else {
+            continue;
+        };
+        edge[idx] = if offset < lit {
+            blend(edge[idx], phase_color, MOON_LIT_ALPHA)
+        } else {
+            blend(edge[idx], [0, 0, 0, 0], MOON_UNLIT_ALPHA)
+        };
+    }
+}
+
+/// Overlay colored arcs for a day's calendar events onto the edge, blending
+/// each event's color over whatever's already there at `alpha`. `start`/`end`
+/// are `date_fraction`-style (`i as f32 / len`) positions around the ring,
+/// same coordinate space the sunrise/sunset gradient uses; `start == end`
+/// lights a single marker LED (a point event), otherwise the arc from
+/// `start` to `end`, clockwise, is filled.
+pub fn render_events(edge: &mut [NeoPixelColor], events: &[(f32, f32, [u8; 3])], alpha: f32) {
+    if edge.is_empty() {
+        return;
+    }
+    let len = edge.len() as f32;
+
+    for &(start, end, [r, g, b]) in events {
+        let color = [r, g, b, 0];
+        let start_idx = (start.rem_euclid(1.0) * len) as usize % edge.len();
+        let end_idx = (end.rem_euclid(1.0) * len) as usize % edge.len();
+
+        let mut i = start_idx;
+        loop {
+            edge[i] = blend(edge[i], color, alpha);
+            if i == end_idx {
+                break;
+            }
+            i = (i + 1) % edge.len();
+        }
+    }
+}
+
+/// Approximate an RGBW NeoPixel value as a displayable RGB color, for outputs
+/// (namely the simulator) with no dedicated white LED: the white channel is
+/// additively blended into each RGB channel. Real RGBW hardware instead
+/// drives `w` on its own fourth LED; this mapping only matters for preview.
+pub fn rgbw_to_rgb(color: NeoPixelColor) -> Rgb888 {
+    let [r, g, b, w] = color;
+    Rgb888::new(
+        r.saturating_add(w),
+        g.saturating_add(w),
+        b.saturating_add(w),
+    )
 }
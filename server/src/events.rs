@@ -0,0 +1,102 @@
+//! Calendar events, overlaid as colored arcs on the edge ring: the same
+//! sunrise/sunset dial the clock already draws can double as an at-a-glance
+//! day schedule.
+
+use std::fmt::{self, Display};
+use std::fs;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A single event to mark on the edge ring. A point event (`end_time: None`)
+/// lights a single marker LED at `start_time`; a ranged event fills the arc
+/// from `start_time` to `end_time`.
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub name: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub color: [u8; 3],
+}
+
+/// A type that can provide today's calendar events.
+pub trait EventSource {
+    /// Get the events to show right now. May return stale data, or none.
+    fn events(&mut self) -> Vec<Event>;
+}
+
+/// The nullary EventSource: provides no events.
+pub struct NullEventSource {}
+
+impl EventSource for NullEventSource {
+    fn events(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
+}
+
+/// Errors encountered while loading events from a JSON file.
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EventDto {
+    name: String,
+    start_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+    color: [u8; 3],
+}
+
+/// An [`EventSource`] backed by a JSON file of events, loaded once at
+/// construction: `[{"name": "...", "start_time": "...", "end_time": "...",
+/// "color": [r, g, b]}, ...]`, `end_time` optional.
+pub struct JsonEventSource {
+    events: Vec<Event>,
+}
+
+impl JsonEventSource {
+    /// Load and parse the events at `path`.
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)?;
+        let dtos: Vec<EventDto> = serde_json::from_str(&contents)?;
+        let events = dtos
+            .into_iter()
+            .map(|dto| Event {
+                name: dto.name,
+                start_time: dto.start_time,
+                end_time: dto.end_time,
+                color: dto.color,
+            })
+            .collect();
+        Ok(Self { events })
+    }
+}
+
+impl EventSource for JsonEventSource {
+    fn events(&mut self) -> Vec<Event> {
+        self.events.clone()
+    }
+}
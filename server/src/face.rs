@@ -1,69 +1,234 @@
-//! Drawing routines for the face of the clock.
+//! Drawing routines for the face of the clock: the [`View`] trait and its
+//! implementations. The aux-area views are cycled between by
+//! [`Renderer::render_aux`](crate::Renderer::render_aux).
 
-use chrono::{DateTime, Datelike, Local, Timelike};
-use embedded_graphics::text::{Alignment, Baseline, TextStyleBuilder};
-use embedded_graphics::Drawable;
+use core::convert::Infallible;
+
+use chrono::{DateTime, TimeZone};
 use embedded_graphics::{
-    geometry::{Point, Size},
-    mono_font::{ascii::FONT_4X6, ascii::FONT_6X9, MonoTextStyle},
-    primitives::{Primitive, PrimitiveStyleBuilder, Rectangle},
-    text::Text,
+    draw_target::DrawTarget,
+    geometry::Point,
+    mono_font::{
+        ascii::{FONT_4X6, FONT_6X9},
+        MonoTextStyle,
+    },
+    pixelcolor::Rgb888,
+    primitives::{PrimitiveStyleBuilder, Rectangle, StyledDrawable},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+    Drawable, Pixel,
 };
-use embedded_graphics_core::pixelcolor::Rgb888;
-use embedded_graphics_core::pixelcolor::RgbColor;
-
-use crate::Displays;
-
-/// Enblish 3-character month abbreviations.
-fn month_en3(number: u32) -> &'static str {
-    match number {
-        1 => "JAN",
-        2 => "FEB",
-        3 => "MAR",
-        4 => "APR",
-        5 => "MAY",
-        6 => "JUN",
-        7 => "JUL",
-        8 => "AUG",
-        9 => "SEP",
-        10 => "OCT",
-        11 => "NOV",
-        12 => "DEC",
-        _ => "???",
+
+use crate::atmosphere::{History, LastMeasurement};
+use crate::calendar::Calendar;
+use crate::store::Trend;
+
+/// A single piece of content drawable onto the face. Implementations return
+/// `false` (and draw nothing) when they have no data to show yet -- e.g. no
+/// atmosphere reading -- so the caller can skip straight to the next view in
+/// the rotation instead of leaving the display blank.
+pub(crate) trait View<D: DrawTarget<Color = Rgb888, Error = Infallible>> {
+    fn draw(&self, target: &mut D) -> bool;
+}
+
+/// The current time, centered; drawn into the top half of the face.
+pub(crate) struct ClockView {
+    pub text: String,
+    pub color: Rgb888,
+}
+
+impl<D: DrawTarget<Color = Rgb888, Error = Infallible>> View<D> for ClockView {
+    fn draw(&self, target: &mut D) -> bool {
+        let style = MonoTextStyle::new(&FONT_6X9, self.color);
+        let text_style = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Top)
+            .build();
+        Text::with_text_style(&self.text, Point::new(15, 0), style, text_style)
+            .draw(target)
+            .expect("infallible");
+        true
     }
 }
 
-/// Render the face of the clock onto the provided DrawTarget.
-pub fn get_clock(time: DateTime<Local>, canvas: &mut impl Displays) {
-    let minute = time.minute();
-    let hour = time.hour();
-    let day = time.day();
-    let month = month_en3(time.month());
-    let year = time.year() % 100;
-    let time = format!("{hour:02}:{minute:02}");
-    let date = format!("{day:02}{month}{year:02}");
-
-    let mut canvas = canvas.face();
-    Rectangle::new(Point::new(0, 0), Size::new(32, 16))
-        .into_styled(
-            PrimitiveStyleBuilder::new()
-                .fill_color(Rgb888::BLACK)
-                .build(),
-        )
-        .draw(&mut canvas)
-        .expect("infallible");
-
-    let time_style = MonoTextStyle::new(&FONT_6X9, Rgb888::WHITE);
-    let date_style = MonoTextStyle::new(&FONT_4X6, Rgb888::WHITE);
-    let style = TextStyleBuilder::new()
-        .alignment(Alignment::Center)
-        .baseline(Baseline::Top)
-        .build();
-
-    Text::with_text_style(&time, Point::new(15, 0), time_style, style)
-        .draw(&mut canvas)
-        .expect("infallible");
-    Text::with_text_style(&date, Point::new(15, 11), date_style, style)
-        .draw(&mut canvas)
-        .expect("infallible");
+/// The date, right-aligned, in whichever [`Calendar`] is configured.
+pub(crate) struct DateView<Tz: TimeZone> {
+    pub time: DateTime<Tz>,
+    pub calendar: Calendar,
+    pub color: Rgb888,
+}
+
+impl<Tz: TimeZone, D: DrawTarget<Color = Rgb888, Error = Infallible>> View<D> for DateView<Tz> {
+    fn draw(&self, target: &mut D) -> bool {
+        let date = self.calendar.format(self.time.date_naive());
+        let style = MonoTextStyle::new(&FONT_4X6, self.color);
+        let text_style = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+
+        Text::with_text_style(&date, Point::new(31, 0), style, text_style)
+            .draw(target)
+            .expect("infallible");
+        true
+    }
+}
+
+/// Current temperature/CO2/relative-humidity reading.
+pub(crate) struct AtmosphereView {
+    pub temperature: Option<LastMeasurement>,
+    pub relative_humidity: Option<LastMeasurement>,
+    pub co2_ppm: Option<LastMeasurement>,
+    pub color: Rgb888,
+}
+
+impl<D: DrawTarget<Color = Rgb888, Error = Infallible>> View<D> for AtmosphereView {
+    fn draw(&self, target: &mut D) -> bool {
+        let (Some(temp), Some(humid), Some(co2)) =
+            (self.temperature, self.relative_humidity, self.co2_ppm)
+        else {
+            tracing::warn!(
+                "missing atmospheric data: temp? {} rh? {} co2? {}",
+                self.temperature.is_some(),
+                self.relative_humidity.is_some(),
+                self.co2_ppm.is_some()
+            );
+            return false;
+        };
+        // In a 4x6 font, we have (32/4=) 8 characters to work with.
+        // 3 for temperature (NNC), four for CO2 (NNNN),
+        // and a one-character space to render humidity into.
+
+        let s = format!("{:<2.0}C {:>4.0}", temp.value, co2.value);
+        let style = MonoTextStyle::new(&FONT_4X6, self.color);
+        let text_style = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Bottom)
+            .build();
+
+        // Points are in raster order, relative to the _lower_ box (not the absolute canvas)!
+        // Lower bounds:
+        let y_max = target.bounding_box().bottom_right().unwrap().y;
+
+        // Bottom line of each character is blank, we can skip it
+        let text_origin = Point::new(31, y_max + 1);
+        Text::with_text_style(&s, text_origin, style, text_style)
+            .draw(target)
+            .expect("infallible");
+
+        // The font is 4x6 but is really only 5 tall (one space line); match that.
+        let humid_level = f32::floor(humid.value / 80.0 * 5.0).clamp(0.0, 5.0) as i32;
+
+        // We can't actually draw a zero-dimensioned box; embedded_graphics uses inclusive bounds.
+        // That's fine.
+        if humid_level == 0 {
+            return true;
+        }
+
+        let lower_left = Point {
+            x: 3 * 4 + 1,
+            y: y_max,
+        };
+        let upper_right = lower_left
+            + Point {
+                x: 3,
+                y: 1 - humid_level,
+            };
+        Rectangle::with_corners(lower_left, upper_right)
+            .draw_styled(
+                &PrimitiveStyleBuilder::new()
+                    .fill_color(self.color)
+                    .stroke_width(0)
+                    .build(),
+                target,
+            )
+            .expect("infallible");
+
+        true
+    }
+}
+
+/// Scale `v` from the range `min..=max` onto a row index in `0..rows`,
+/// shared by [`GraphView`] and [`TrendView`]. Larger values map to larger
+/// rows; callers that plot larger values higher on screen flip the row
+/// themselves. Falls back to the middle row when `min == max`, since there's
+/// no range to scale against.
+fn scale_to_row(v: f32, min: f32, max: f32, rows: i32) -> i32 {
+    if max > min {
+        (((v - min) / (max - min)) * (rows - 1) as f32).round() as i32
+    } else {
+        (rows - 1) / 2
+    }
+}
+
+/// Sparkline of a metric's recent history, stretched or downsampled across
+/// the available columns and auto-scaled to the min/max of the present
+/// samples. Draws nothing (returning `false`) if there isn't at least one
+/// sample to plot yet.
+pub(crate) struct GraphView<'a, const N: usize> {
+    pub history: &'a History<N>,
+    pub color: Rgb888,
+}
+
+impl<'a, const N: usize, D: DrawTarget<Color = Rgb888, Error = Infallible>> View<D>
+    for GraphView<'a, N>
+{
+    fn draw(&self, target: &mut D) -> bool {
+        /// Rows of vertical resolution for the graph, centered in the 7px-tall aux area.
+        const GRAPH_ROWS: i32 = 5;
+        const ROW_MARGIN: i32 = 1;
+
+        let Some((min, max)) = self.history.min_max() else {
+            return false;
+        };
+        let samples: Vec<Option<f32>> = self.history.iter().collect();
+
+        let width = target.bounding_box().size.width as usize;
+        let pixels = (0..width).filter_map(|col| {
+            let idx = if width <= 1 {
+                0
+            } else {
+                col * (samples.len() - 1) / (width - 1)
+            };
+            let v = samples[idx]?;
+            let row = scale_to_row(v, min, max, GRAPH_ROWS);
+            // Larger values plot higher, i.e. toward row 0.
+            let y = ROW_MARGIN + (GRAPH_ROWS - 1 - row);
+            Some(Pixel(Point::new(col as i32, y), self.color))
+        });
+        target.draw_iter(pixels).expect("infallible");
+
+        true
+    }
+}
+
+/// Full-face scrolling trend of one atmosphere metric's recent history, one
+/// downsampled sample per column, scaled to fill the whole display height.
+/// Used for the "measurements" presentation, which replaces the clock and
+/// aux carousel entirely rather than sharing the face with them.
+pub(crate) struct TrendView {
+    pub trend: Trend,
+    pub color: Rgb888,
+}
+
+impl<D: DrawTarget<Color = Rgb888, Error = Infallible>> View<D> for TrendView {
+    fn draw(&self, target: &mut D) -> bool {
+        let height = target.bounding_box().size.height as i32;
+        let (min, max) = (self.trend.min, self.trend.max);
+
+        let pixels = self
+            .trend
+            .samples
+            .iter()
+            .enumerate()
+            .filter_map(|(col, v)| {
+                let v = (*v)?;
+                let row = scale_to_row(v, min, max, height);
+                // Larger values plot higher, i.e. toward row 0.
+                let y = height - 1 - row;
+                Some(Pixel(Point::new(col as i32, y), self.color))
+            });
+        target.draw_iter(pixels).expect("infallible");
+
+        true
+    }
 }
@@ -8,42 +8,69 @@
 //! - gcc-aarch64-linux-gnu for cross-compilation
 //! - g++-aarch64-linux-gnu for cross-compilation
 //!
-use std::{convert::Infallible, f32::consts::PI};
+use std::{convert::Infallible, f32::consts::PI, time::Duration};
 
 #[cfg(feature = "web")]
 pub mod web;
 
+pub mod calendar;
 pub mod context;
+pub(crate) mod face;
+pub mod events;
+pub mod moon;
 pub mod riseset;
+pub mod store;
 
 #[cfg(feature = "simulator")]
 pub mod simulator;
 
 pub mod atmosphere;
 
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+
 pub(crate) mod drawing;
 
+pub(crate) mod raster;
+
 #[cfg(feature = "hardware")]
 pub mod led_displays;
 
 use embedded_graphics::{
     geometry::{Point, Size},
-    mono_font::{
-        ascii::{FONT_4X6, FONT_6X9},
-        MonoTextStyle,
-    },
     pixelcolor::RgbColor,
-    primitives::{Primitive, PrimitiveStyleBuilder, Rectangle, StyledDrawable},
-    text::{Alignment, Baseline, Text, TextStyleBuilder},
+    primitives::{Primitive, PrimitiveStyleBuilder, Rectangle},
     Drawable,
 };
 
-use atmosphere::{AtmosphereSampler, LastMeasurement};
-use chrono::{DateTime, Datelike, Timelike};
+use atmosphere::{AtmospherePublisher, AtmosphereSampler, History, LastMeasurement};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, Timelike};
 use embedded_graphics::{
     draw_target::{DrawTarget, DrawTargetExt},
     pixelcolor::Rgb888,
 };
+use events::EventSource;
+use face::View;
+use store::{HistoryStore, Metric};
+
+/// Number of columns of history kept per metric, matching the 32px-wide aux area.
+const HISTORY_LEN: usize = 32;
+
+/// Minimum interval between accepted samples in a metric's history.
+const HISTORY_PUSH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How strongly an event's color is blended over the edge's day/night glow;
+/// high enough to read clearly as a marker without fully hiding the
+/// underlying brightness.
+const EVENT_OVERLAY_ALPHA: f32 = 0.85;
+
+/// Marker color for a temporal-hour boundary that falls during the day:
+/// full-brightness white on every channel.
+const DAY_TICK_COLOR: NeoPixelColor = [255, 255, 255, 255];
+
+/// Marker color for a temporal-hour boundary that falls at night: a dimmer
+/// cool blue, distinguishable from the day markers at a glance.
+const NIGHT_TICK_COLOR: NeoPixelColor = [40, 80, 255, 40];
 
 /// Alias for a color of NeoPixel.
 pub type NeoPixelColor = [u8; 4];
@@ -65,9 +92,35 @@ pub trait Displays {
     fn flush(&mut self) -> Result<(), String>;
 }
 
+/// A page of auxiliary information shown in the bottom half of the face,
+/// advancing to the next enabled page every [`RendererSettings::display_cycles`]
+/// frames. Pages with no data available to show (e.g. no atmosphere sample yet)
+/// are skipped rather than shown blank.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AuxPage {
+    /// Current temperature/CO2/humidity reading.
+    Atmosphere,
+    /// The date, in [`RendererSettings::calendar`].
+    Date,
+    /// Sparkline of recent temperature history.
+    TempGraph,
+    /// Sparkline of recent CO2 history.
+    Co2Graph,
+}
+
+/// Which presentation the face is in: the everyday clock (with the
+/// [`AuxPage`] carousel below it), or a full-face scrolling trend of one
+/// metric's recent history from the [`Renderer`]'s [`HistoryStore`], in the
+/// spirit of a raspi-oled "measurements" view.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Presentation {
+    Clock,
+    Measurements(Metric),
+}
+
 /// Provides the core rendering setting(s).
 #[cfg_attr(feature = "web", wasm_bindgen::prelude::wasm_bindgen)]
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct RendererSettings {
     /// Minimum edge pixel brightness during daylight
     pub min_daylight: f32,
@@ -81,10 +134,47 @@ pub struct RendererSettings {
     /// How many cycles (frames) to display each piece of auxiliary data.
     pub display_cycles: usize,
 
+    /// Show temporal ("seasonal") hours instead of civil time: daylight and
+    /// night are each divided into 12 equal hours, so an "hour" is longer in
+    /// summer days than winter ones. Also marks the 12 daylight and 12 night
+    /// divisions as tick points around the NeoPixel edge.
+    pub temporal_hours: bool,
+
+    /// When `temporal_hours` is set, show the traditional Latin name for
+    /// daylight hours that have one (e.g. "TERCE") instead of a bare hour
+    /// number.
+    pub seasonal_hour_names: bool,
+
+    /// Warmth of the daylight edge color, from 0.0 (neutral white) to 1.0
+    /// (warm/incandescent).
+    pub daylight_warmth: f32,
+    /// Balance of the night edge color, from 0.0 (pure blue nightlight) to
+    /// 1.0 (neutral white).
+    pub night_white_balance: f32,
+
     /// Color to use for rendering the matrix.
     pub matrix_r: u8,
     pub matrix_g: u8,
     pub matrix_b: u8,
+
+    /// Pages to rotate through in the aux area, in order. Not exposed over the
+    /// wasm ABI; the web renderer always uses the default list.
+    #[cfg_attr(feature = "web", wasm_bindgen(skip))]
+    pub aux_pages: Vec<AuxPage>,
+
+    /// Calendar to render [`AuxPage::Date`] in. Not exposed over the wasm
+    /// ABI, like `aux_pages`.
+    #[cfg_attr(feature = "web", wasm_bindgen(skip))]
+    pub calendar: calendar::Calendar,
+
+    /// Which presentation the face is in; see [`Presentation`]. Not exposed
+    /// over the wasm ABI, like `aux_pages`.
+    #[cfg_attr(feature = "web", wasm_bindgen(skip))]
+    pub presentation: Presentation,
+
+    /// How many hours of history a [`Presentation::Measurements`] trend
+    /// covers.
+    pub measurement_window_hours: u32,
 }
 
 /// State of a renderer.
@@ -95,6 +185,21 @@ pub struct Renderer {
     last_co2_ppm: Option<LastMeasurement>,
     last_temperature: Option<LastMeasurement>,
     last_relative_humidity: Option<LastMeasurement>,
+
+    co2_history: History<HISTORY_LEN>,
+    temperature_history: History<HISTORY_LEN>,
+    relative_humidity_history: History<HISTORY_LEN>,
+
+    /// Where to publish newly-observed readings, if anywhere (e.g. MQTT).
+    publisher: Option<Box<dyn AtmospherePublisher>>,
+
+    /// Where to durably record every sample, if anywhere, for the
+    /// [`Presentation::Measurements`] trend view.
+    store: Option<Box<dyn HistoryStore>>,
+
+    /// Where to fetch today's calendar events, if anywhere, to overlay on
+    /// the edge ring.
+    events: Option<Box<dyn EventSource>>,
 }
 
 impl From<RendererSettings> for Renderer {
@@ -105,6 +210,12 @@ impl From<RendererSettings> for Renderer {
             last_relative_humidity: None,
             last_co2_ppm: None,
             last_temperature: None,
+            co2_history: History::new(HISTORY_PUSH_INTERVAL),
+            temperature_history: History::new(HISTORY_PUSH_INTERVAL),
+            relative_humidity_history: History::new(HISTORY_PUSH_INTERVAL),
+            publisher: None,
+            store: None,
+            events: None,
         }
     }
 }
@@ -138,9 +249,22 @@ impl Default for RendererSettings {
             latitude: 39.0,
             longitude: -77.0,
             display_cycles: 60,
+            temporal_hours: false,
+            seasonal_hour_names: false,
+            daylight_warmth: 0.3,
+            night_white_balance: 0.0,
             matrix_r: 255,
             matrix_g: 255,
             matrix_b: 255,
+            aux_pages: vec![
+                AuxPage::Atmosphere,
+                AuxPage::Date,
+                AuxPage::TempGraph,
+                AuxPage::Co2Graph,
+            ],
+            calendar: calendar::Calendar::default(),
+            presentation: Presentation::Clock,
+            measurement_window_hours: 6,
         }
     }
 }
@@ -151,6 +275,25 @@ impl Renderer {
         &mut self.settings
     }
 
+    /// Set where newly-observed atmosphere readings should be published (e.g.
+    /// to an MQTT broker), replacing any previous publisher.
+    pub fn set_publisher(&mut self, publisher: Box<dyn AtmospherePublisher>) {
+        self.publisher = Some(publisher);
+    }
+
+    /// Set where newly-observed atmosphere readings should be durably
+    /// recorded for the [`Presentation::Measurements`] trend view, replacing
+    /// any previous store.
+    pub fn set_history_store(&mut self, store: Box<dyn HistoryStore>) {
+        self.store = Some(store);
+    }
+
+    /// Set where today's calendar events should be fetched from, to overlay
+    /// on the edge ring, replacing any previous source.
+    pub fn set_event_source(&mut self, events: Box<dyn EventSource>) {
+        self.events = Some(events);
+    }
+
     /// Update the displays with the current data.
     pub fn render<Tz, D, A>(&mut self, displays: &mut D, atmosphere: &mut A, now: DateTime<Tz>)
     where
@@ -175,7 +318,7 @@ impl Renderer {
         )
     }
 
-    fn render_edge<Tz, D>(&self, displays: &mut D, now: DateTime<Tz>)
+    fn render_edge<Tz, D>(&mut self, displays: &mut D, now: DateTime<Tz>)
     where
         Tz: chrono::TimeZone,
         D: Displays,
@@ -207,47 +350,103 @@ impl Renderer {
         });
 
         let daylight = set - rise;
+        let night = (rise + 1.0) - set;
 
         let len = output.len() as f32;
+
+        // When showing temporal hours, mark the twelve daytime divisions
+        // (interpolating sunrise to sunset) and the twelve nighttime
+        // divisions (sunset to the next sunrise, wrapping past midnight) as
+        // tick points around the edge, in distinct colors so day and night
+        // markers read apart at a glance.
+        let to_index = |fraction: f32| {
+            let fraction = fraction.rem_euclid(1.0);
+            ((fraction * len) as usize).min(output.len().saturating_sub(1))
+        };
+        let day_tick_indices: [usize; 12] =
+            core::array::from_fn(|k| to_index(rise + daylight * (k as f32 / 12.0)));
+        let night_tick_indices: [usize; 12] =
+            core::array::from_fn(|k| to_index(set + night * (k as f32 / 12.0)));
+
         for (i, px) in output.iter_mut().enumerate() {
+            if self.settings.temporal_hours {
+                if day_tick_indices.contains(&i) {
+                    *px = DAY_TICK_COLOR;
+                    continue;
+                }
+                if night_tick_indices.contains(&i) {
+                    *px = NIGHT_TICK_COLOR;
+                    continue;
+                }
+            }
             // The [0, 1)-bounded fraction of the day this point is at.
             let date_fraction = i as f32 / len;
-            // What fraction of _daylight_ has passed at this point?
-            // (May be negative or greater than 1)
-            let day_fraction = (date_fraction - rise) / daylight;
-            if (0.0..=1.0).contains(&day_fraction) {
-                // During daylight hours.
-                // Make a nice curve via sin:
-                let sin = (day_fraction * PI).sin();
-                // But then make sure it meets a minimum brightness:
-                let f = self.settings.min_daylight + sin * (1.0 - self.settings.min_daylight);
-
-                // Then re-range to 0..=255.
-                let amt = (f * 255.0).clamp(0.0, 255.0) as u8;
-                tracing::trace!(
-                    "point {i:03}:   day fraction {day_fraction:.2}, sin {sin:.2}, amt {amt:0}",
-                );
-                // TODO: Using RGB so it shows up on the simulator.
-                // How do we use / render W channel?
-                *px = [amt, amt, amt, amt];
+
+            // Reconstruct the local instant this point represents, so we can
+            // look up the sun's elevation there and grade civil twilight
+            // smoothly instead of hard-switching at rise/set.
+            let day_seconds = (date_fraction * 86_400.0).round() as i64;
+            let local_naive = NaiveDateTime::new(now.date_naive(), NaiveTime::MIN)
+                + chrono::Duration::seconds(day_seconds);
+            let pixel_time = now
+                .timezone()
+                .from_local_datetime(&local_naive)
+                .single()
+                .unwrap_or_else(|| now.clone());
+            let elevation =
+                riseset::solar_elevation(&pixel_time, self.settings.latitude, self.settings.longitude);
+            let twilight = riseset::twilight_fraction(elevation);
+
+            // Brightness still follows the same sin-shaped curve relative to
+            // sunrise/sunset as before, clamped to a sensible domain outside
+            // of daylight/night respectively.
+            let day_fraction = ((date_fraction - rise) / daylight).clamp(0.0, 1.0);
+            let day_sin = (day_fraction * PI).sin();
+            let day_f = self.settings.min_daylight + day_sin * (1.0 - self.settings.min_daylight);
+
+            let night_point = if date_fraction < rise {
+                date_fraction + 1.0
             } else {
-                // Normalize to "tomorrow night"
-                let night_point = if date_fraction < rise {
-                    date_fraction + 1.0
-                } else {
-                    date_fraction
-                };
-                let night_fraction = (night_point - set) / ((rise + 1.0) - set);
-                let sin = (night_fraction * PI).sin();
-                // and subtract that out from the maximum:
-                let f = self.settings.max_nightlight - (self.settings.max_nightlight * sin);
-                let amt = (f * 255.0).clamp(0.0, 255.0) as u8;
-                tracing::trace!(
-                    "point {i:03}: night fraction {night_fraction:.2}, sin {sin:.2}, amt {amt:0}",
-                );
-                // Night is only blue, for now.
-                *px = [0, 0, amt, 0];
-            }
+                date_fraction
+            };
+            let night_fraction = ((night_point - set) / ((rise + 1.0) - set)).clamp(0.0, 1.0);
+            let night_sin = (night_fraction * PI).sin();
+            let night_f = self.settings.max_nightlight - (self.settings.max_nightlight * night_sin);
+
+            let day_color = drawing::daylight_rgbw(day_f, self.settings.daylight_warmth);
+            let night_color = drawing::night_rgbw(night_f, self.settings.night_white_balance);
+
+            tracing::trace!(
+                "point {i:03}: elevation {elevation:.2}, twilight {twilight:.2}, day brightness {day_f:.2}, night brightness {night_f:.2}",
+            );
+            *px = drawing::blend(night_color, day_color, twilight);
+        }
+
+        // Overlay a small lunar dial near the top of the ring, independent of
+        // the sun-driven glow computed above.
+        let (illuminated_fraction, _phase) = moon::phase(now.with_timezone(&chrono::Utc));
+        let arc_pixels = (output.len() / 16).max(3);
+        drawing::render_moon(output, arc_pixels, illuminated_fraction, [200, 200, 200, 80]);
+
+        // Overlay today's calendar events, if a source is configured, as
+        // colored arcs at the same local time-of-day coordinate used above.
+        if let Some(source) = &mut self.events {
+            let today = now.date_naive();
+            let to_fraction = |dt: DateTime<chrono::Utc>| {
+                let t = dt.with_timezone(&now.timezone()).time();
+                (t.hour() * 60 + t.minute()) as f32 / (24 * 60) as f32
+            };
+            let arcs: Vec<(f32, f32, [u8; 3])> = source
+                .events()
+                .into_iter()
+                .filter(|e| e.start_time.with_timezone(&now.timezone()).date_naive() == today)
+                .map(|e| {
+                    let start = to_fraction(e.start_time);
+                    let end = e.end_time.map(to_fraction).unwrap_or(start);
+                    (start, end, e.color)
+                })
+                .collect();
+            drawing::render_events(output, &arcs, EVENT_OVERLAY_ALPHA);
         }
     }
 
@@ -265,13 +464,25 @@ impl Renderer {
         );
         let co2_update = LastMeasurement::update(&mut self.last_co2_ppm, s.timestamp, s.co2_ppm);
 
+        self.temperature_history.push(s.timestamp, s.temperature);
+        self.relative_humidity_history
+            .push(s.timestamp, s.relative_humidity);
+        self.co2_history.push(s.timestamp, s.co2_ppm);
+
+        if let Some(store) = &mut self.store {
+            store.append(&s);
+        }
+
         if temp_update || rh_update || co2_update {
             tracing::info!(
                 "new atomospheric reading: {} PPM CO2, {}% RH, {}°C",
                 s.co2_ppm.unwrap_or(0.0),
                 s.relative_humidity.unwrap_or(0.0),
                 s.temperature.unwrap_or(0.0),
-            )
+            );
+            if let Some(publisher) = &mut self.publisher {
+                publisher.publish(&s);
+            }
         }
     }
 
@@ -281,9 +492,11 @@ impl Renderer {
         D: Displays,
         A: AtmosphereSampler,
     {
-        let minute = time.minute();
-        let hour = time.hour();
-        let time_str = format!("{hour:02}:{minute:02}");
+        // Sampling (and recording history) happens every frame regardless of
+        // presentation, so the history store stays current even while
+        // showing the clock.
+        self.update_atmo(atmosphere);
+        self.display_cycle = self.display_cycle.wrapping_add(1);
 
         let mut canvas = displays.face();
         Rectangle::new(Point::new(0, 0), Size::new(32, 16))
@@ -295,136 +508,123 @@ impl Renderer {
             .draw(&mut canvas)
             .expect("infallible");
 
-        // The time always goes into the upper half of the display;
-        // auxiliary data into the bottom.
-        {
-            let time_style = MonoTextStyle::new(&FONT_6X9, self.matrix_color());
-            let style = TextStyleBuilder::new()
-                .alignment(Alignment::Center)
-                .baseline(Baseline::Top)
-                .build();
-            Text::with_text_style(&time_str, Point::new(15, 0), time_style, style)
-                .draw(&mut canvas)
-                .expect("infallible");
-        }
-        let aux_size = Size::new(32, 7);
-        let mut aux_crop = canvas.cropped(&Rectangle::new(Point::new(0, 9), aux_size));
-        let mut aux = aux_crop.clipped(&Rectangle::new(Point::new(0, 0), aux_size));
+        match self.settings.presentation {
+            Presentation::Clock => {
+                let time_str = if self.settings.temporal_hours {
+                    let (hour, is_day) = riseset::temporal_hour(
+                        &time,
+                        self.settings.latitude,
+                        self.settings.longitude,
+                    );
+                    let name = self
+                        .settings
+                        .seasonal_hour_names
+                        .then(|| riseset::canonical_hour_name(hour, is_day))
+                        .flatten();
+                    match name {
+                        Some(name) => name.to_string(),
+                        None => format!("{}{hour:02}", if is_day { 'D' } else { 'N' }),
+                    }
+                } else {
+                    let minute = time.minute();
+                    let hour = time.hour();
+                    format!("{hour:02}:{minute:02}")
+                };
 
-        self.update_atmo(atmosphere);
-        if !self.render_atmo(&mut aux) {
-            // Fall back to rendering date
-            self.render_date(&mut aux, time);
+                // The time always goes into the upper half of the display;
+                // auxiliary data into the bottom.
+                face::ClockView {
+                    text: time_str,
+                    color: self.matrix_color(),
+                }
+                .draw(&mut canvas);
+
+                let aux_size = Size::new(32, 7);
+                let mut aux_crop = canvas.cropped(&Rectangle::new(Point::new(0, 9), aux_size));
+                let mut aux = aux_crop.clipped(&Rectangle::new(Point::new(0, 0), aux_size));
+                self.render_aux(&mut aux, time);
+            }
+            Presentation::Measurements(metric) => {
+                self.render_measurements(&mut canvas, metric);
+            }
         }
     }
 
-    /// Render the date into the provided space.
-    fn render_date<Tz>(
+    /// Render a full-face scrolling trend of `metric`'s recent history from
+    /// the configured [`HistoryStore`], for [`Presentation::Measurements`].
+    /// Does nothing (beyond the already-cleared face) if no store is
+    /// configured or it has no history yet.
+    fn render_measurements(
         &self,
         canvas: &mut impl DrawTarget<Color = Rgb888, Error = Infallible>,
-        time: DateTime<Tz>,
-    ) where
-        Tz: chrono::TimeZone,
-    {
-        let date = format!(
-            "{:02}{}{:02}",
-            time.day(),
-            month_en3(time.month()),
-            time.year() % 100
-        );
-        let date_style = MonoTextStyle::new(&FONT_4X6, self.matrix_color());
-        let style = TextStyleBuilder::new()
-            .alignment(Alignment::Right)
-            .baseline(Baseline::Top)
-            .build();
-
-        Text::with_text_style(&date, Point::new(31, 0), date_style, style)
-            .draw(canvas)
-            .expect("infallible");
-    }
-
-    fn render_atmo(&self, aux: &mut impl DrawTarget<Color = Rgb888, Error = Infallible>) -> bool {
-        let (Some(temp), Some(humid), Some(co2)) = (
-            self.last_temperature,
-            self.last_relative_humidity,
-            self.last_co2_ppm,
-        ) else {
-            tracing::warn!(
-                "missing atmospheric data: temp? {} rh? {} co2? {}",
-                self.last_temperature.is_some(),
-                self.last_relative_humidity.is_some(),
-                self.last_co2_ppm.is_some()
-            );
-            return false;
+        metric: Metric,
+    ) {
+        let Some(store) = &self.store else {
+            tracing::warn!("measurements presentation selected but no history store configured");
+            return;
         };
-        // In a 4x6 font, we have (32/4=) 8 characters to work with.
-        // 3 for temperature (NNC), four for CO2 (NNNN),
-        // and a one-character space to render humidity into.
-
-        let s = format!("{:<2.0}C {:>4.0}", temp.value, co2.value);
-        let temp_style = MonoTextStyle::new(&FONT_4X6, self.matrix_color());
-        let style = TextStyleBuilder::new()
-            .alignment(Alignment::Right)
-            .baseline(Baseline::Bottom)
-            .build();
-
-        // Points are in raster order, relative to the _lower_ box (not the absolute canvas)!
-        // Lower bounds:
-        let y_max = aux.bounding_box().bottom_right().unwrap().y;
-
-        // Bottom line of each character is blank, we can skip it
-        let text_origin = Point::new(31, y_max + 1);
-        Text::with_text_style(&s, text_origin, temp_style, style)
-            .draw(aux)
-            .expect("infallible");
-
-        // The font is 4x6 but is really only 5 tall (one space line); match that.
-        let humid_level = f32::floor(humid.value / 80.0 * 5.0).clamp(0.0, 5.0) as i32;
-
-        // We can't actually draw a zero-dimensioned box; embedded_graphics uses inclusive bounds.
-        // That's fine.
-        if humid_level == 0 {
-            return true;
-        }
-
-        let lower_left = Point {
-            x: 3 * 4 + 1,
-            y: y_max,
+        let width = canvas.bounding_box().size.width as usize;
+        let window = Duration::from_secs(self.settings.measurement_window_hours as u64 * 3600);
+        let Some(trend) = store.trend(metric, window, width) else {
+            tracing::warn!("no history yet for {metric:?} measurements view");
+            return;
         };
-        let upper_right = lower_left
-            + Point {
-                x: 3,
-                y: 1 - humid_level,
-            };
-        Rectangle::with_corners(lower_left, upper_right)
-            .draw_styled(
-                &PrimitiveStyleBuilder::new()
-                    .fill_color(self.matrix_color())
-                    .stroke_width(0)
-                    .build(),
-                aux,
-            )
-            .expect("infallible");
 
-        true
+        face::TrendView {
+            trend,
+            color: self.matrix_color(),
+        }
+        .draw(canvas);
     }
-}
 
-/// Enblish 3-character month abbreviations.
-fn month_en3(number: u32) -> &'static str {
-    match number {
-        1 => "JAN",
-        2 => "FEB",
-        3 => "MAR",
-        4 => "APR",
-        5 => "MAY",
-        6 => "JUN",
-        7 => "JUL",
-        8 => "AUG",
-        9 => "SEP",
-        10 => "OCT",
-        11 => "NOV",
-        12 => "DEC",
-        _ => "???",
+    /// Render whichever [`AuxPage`] is current, advancing through
+    /// `settings.aux_pages` every `settings.display_cycles` frames and
+    /// skipping pages that have no data to show.
+    fn render_aux<Tz>(
+        &self,
+        aux: &mut impl DrawTarget<Color = Rgb888, Error = Infallible>,
+        time: DateTime<Tz>,
+    ) where
+        Tz: chrono::TimeZone,
+    {
+        let pages = &self.settings.aux_pages;
+        if pages.is_empty() {
+            return;
+        }
+        let cycles = self.settings.display_cycles.max(1);
+        let start = (self.display_cycle / cycles) % pages.len();
+        let color = self.matrix_color();
+
+        let rendered = (0..pages.len()).any(|offset| {
+            let page = pages[(start + offset) % pages.len()];
+            match page {
+                AuxPage::Atmosphere => face::AtmosphereView {
+                    temperature: self.last_temperature,
+                    relative_humidity: self.last_relative_humidity,
+                    co2_ppm: self.last_co2_ppm,
+                    color,
+                }
+                .draw(aux),
+                AuxPage::Date => face::DateView {
+                    time: time.clone(),
+                    calendar: self.settings.calendar,
+                    color,
+                }
+                .draw(aux),
+                AuxPage::TempGraph => face::GraphView {
+                    history: &self.temperature_history,
+                    color,
+                }
+                .draw(aux),
+                AuxPage::Co2Graph => face::GraphView {
+                    history: &self.co2_history,
+                    color,
+                }
+                .draw(aux),
+            }
+        });
+        if !rendered {
+            tracing::warn!("no aux page had data to render this frame");
+        }
     }
 }
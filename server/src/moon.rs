@@ -0,0 +1,78 @@
+//! Moon phase calculations, parallel to the sun rise/set equations in
+//! [`riseset`](crate::riseset).
+//!
+//! Equations are Meeus's low-precision approximations (_Astronomical
+//! Algorithms_, ch. 49) for the moon's phase angle, good to a few degrees --
+//! plenty for a decorative phase indicator.
+
+use chrono::{DateTime, Utc};
+
+/// One of the eight traditionally-named moon phases, in order of increasing
+/// age (New -> Full -> New again).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+const NAMED_PHASES: [MoonPhase; 8] = [
+    MoonPhase::New,
+    MoonPhase::WaxingCrescent,
+    MoonPhase::FirstQuarter,
+    MoonPhase::WaxingGibbous,
+    MoonPhase::Full,
+    MoonPhase::WaningGibbous,
+    MoonPhase::LastQuarter,
+    MoonPhase::WaningCrescent,
+];
+
+impl MoonPhase {
+    /// Bucket a (corrected) phase angle (degrees, 0 = new moon, 180 = full
+    /// moon) into the nearest of the eight named phases.
+    fn from_elongation(angle: f32) -> Self {
+        let idx = (angle.rem_euclid(360.0) / 45.0).round() as usize % 8;
+        NAMED_PHASES[idx]
+    }
+}
+
+/// Julian Ephemeris Day for `date_time`, ignoring the (sub-minute) difference
+/// between Terrestrial Time and UTC -- not worth the complexity for a
+/// decorative phase indicator.
+fn jde(date_time: DateTime<Utc>) -> f64 {
+    let unix_seconds =
+        date_time.timestamp() as f64 + date_time.timestamp_subsec_nanos() as f64 / 1e9;
+    // 1970-01-01T00:00:00Z is JD 2440587.5.
+    2440587.5 + unix_seconds / 86400.0
+}
+
+/// The moon's illuminated fraction (0.0 = new, 1.0 = full) and named phase at
+/// `date_time`.
+pub fn phase(date_time: DateTime<Utc>) -> (f32, MoonPhase) {
+    let t = (jde(date_time) / 36525.0) as f32;
+
+    // Mean elongation of the moon from the sun.
+    let d = 297.8502042 + 445267.111_5686 * t - 0.00163 * t * t + t * t * t / 545868.0;
+    // Sun's mean anomaly.
+    let m = 357.5291092 + 35999.050_2909 * t - 0.0001536 * t * t;
+    // Moon's mean anomaly.
+    let m_prime = 134.9634114 + 477198.867_6313 * t + 0.008997 * t * t;
+
+    let deg_sin = |deg: f32| deg.to_radians().sin();
+    let i = (d
+        + 6.289 * deg_sin(m_prime)
+        - 2.1 * deg_sin(m)
+        + 1.274 * deg_sin(2.0 * d - m_prime)
+        + 0.658 * deg_sin(2.0 * d)
+        + 0.241 * deg_sin(2.0 * m_prime)
+        + 0.110 * deg_sin(d))
+    .rem_euclid(360.0);
+
+    let illuminated_fraction = (1.0 - i.to_radians().cos()) / 2.0;
+    (illuminated_fraction, MoonPhase::from_elongation(i))
+}
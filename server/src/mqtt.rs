@@ -0,0 +1,126 @@
+//! MQTT integration: feed remote sensor readings into an [`AtmosphereSampler`],
+//! and publish the clock's own readings for other subscribers (e.g. Home
+//! Assistant) to consume.
+//!
+//! Built on `rumqttc`'s synchronous client, following the same
+//! background-thread-plus-cache pattern as the NWS sampler in
+//! [`atmosphere::usgov`](crate::atmosphere::usgov).
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use chrono::Utc;
+use rumqttc::{Client, Event, MqttOptions, Packet, QoS};
+
+use crate::atmosphere::{AtmospherePublisher, AtmosphereSample, AtmosphereSampler};
+
+/// Topics to subscribe to (for [`MqttAtmosphereSampler`]) or publish on (for
+/// [`MqttAtmospherePublisher`]) for each metric. A `None` field disables that
+/// metric.
+#[derive(Clone, Debug, Default)]
+pub struct Topics {
+    pub temperature: Option<String>,
+    pub relative_humidity: Option<String>,
+    pub co2_ppm: Option<String>,
+}
+
+/// An [`AtmosphereSampler`] fed by subscribing to MQTT topics, rather than
+/// local hardware — e.g. a remote sensor publishing readings elsewhere in the
+/// house.
+pub struct MqttAtmosphereSampler {
+    latest: Arc<Mutex<AtmosphereSample>>,
+}
+
+impl MqttAtmosphereSampler {
+    /// Connect to the broker at `options` and subscribe to `topics` on a
+    /// background thread, caching whatever value each topic last published.
+    pub fn new(options: MqttOptions, topics: Topics) -> Self {
+        let (client, mut connection) = Client::new(options, 10);
+        for topic in [&topics.temperature, &topics.relative_humidity, &topics.co2_ppm]
+            .into_iter()
+            .flatten()
+        {
+            if let Err(e) = client.subscribe(topic, QoS::AtMostOnce) {
+                tracing::warn!("failed to subscribe to {topic}: {e}");
+            }
+        }
+
+        let latest = Arc::new(Mutex::new(AtmosphereSample::default()));
+        let thread_latest = latest.clone();
+        thread::spawn(move || {
+            for notification in connection.iter() {
+                let Ok(Event::Incoming(Packet::Publish(publish))) = notification else {
+                    continue;
+                };
+                let Ok(value) = std::str::from_utf8(&publish.payload)
+                    .unwrap_or("")
+                    .trim()
+                    .parse::<f32>()
+                else {
+                    tracing::warn!("non-numeric MQTT payload on {}", publish.topic);
+                    continue;
+                };
+
+                let mut sample = thread_latest.lock().expect("MQTT sample mutex poisoned");
+                sample.timestamp = Utc::now();
+                if topics.temperature.as_deref() == Some(publish.topic.as_str()) {
+                    sample.temperature = Some(value);
+                } else if topics.relative_humidity.as_deref() == Some(publish.topic.as_str()) {
+                    sample.relative_humidity = Some(value);
+                } else if topics.co2_ppm.as_deref() == Some(publish.topic.as_str()) {
+                    sample.co2_ppm = Some(value);
+                }
+            }
+        });
+
+        MqttAtmosphereSampler { latest }
+    }
+}
+
+impl AtmosphereSampler for MqttAtmosphereSampler {
+    fn sample(&mut self) -> AtmosphereSample {
+        *self.latest.lock().expect("MQTT sample mutex poisoned")
+    }
+}
+
+/// An [`AtmospherePublisher`] that pushes each new reading (e.g. from a local
+/// SCD30) to MQTT topics, for Home Assistant or other subscribers.
+pub struct MqttAtmospherePublisher {
+    client: Client,
+    topics: Topics,
+}
+
+impl MqttAtmospherePublisher {
+    /// Connect to the broker at `options`, publishing under `topics`.
+    pub fn new(options: MqttOptions, topics: Topics) -> Self {
+        let (client, mut connection) = Client::new(options, 10);
+        // Drive the event loop so publishes actually get flushed to the
+        // broker; we don't care about the incoming events on this side.
+        thread::spawn(move || for _notification in connection.iter() {});
+        MqttAtmospherePublisher { client, topics }
+    }
+}
+
+impl AtmospherePublisher for MqttAtmospherePublisher {
+    fn publish(&mut self, sample: &AtmosphereSample) {
+        let readings = [
+            (self.topics.temperature.as_deref(), sample.temperature),
+            (
+                self.topics.relative_humidity.as_deref(),
+                sample.relative_humidity,
+            ),
+            (self.topics.co2_ppm.as_deref(), sample.co2_ppm),
+        ];
+        for (topic, value) in readings {
+            let (Some(topic), Some(value)) = (topic, value) else {
+                continue;
+            };
+            if let Err(e) = self
+                .client
+                .publish(topic, QoS::AtMostOnce, false, value.to_string())
+            {
+                tracing::warn!("failed to publish to {topic}: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! A backend-agnostic, analytic coverage rasterizer for the edge ring's arc
+//! wedges and the matrix's round LED dots.
+//!
+//! [`crate::web::WebDisplays`] used to paint these with HTML canvas path
+//! fills (smooth, but canvas-only), while [`crate::simulator::SimDisplays`]
+//! assigned one LED's flat color to one simulator pixel with no softening
+//! at all, and had no equivalent for the matrix's dots. This module computes
+//! fractional per-pixel coverage for both shapes directly -- in the spirit
+//! of a `forma`-style CPU path renderer -- into a plain RGB [`Canvas`], so
+//! every backend gets the same anti-aliased look regardless of how it
+//! ultimately presents pixels.
+
+use embedded_graphics::{
+    pixelcolor::{Rgb888, RgbColor},
+    prelude::Point,
+    Pixel,
+};
+
+/// Fractional coverage (0.0 = fully outside, 1.0 = fully inside) of a round
+/// dot of `radius` centered at `center`, sampled at pixel center `px`. The
+/// `+ 0.5` softens the boundary over roughly one pixel of distance, so a
+/// pixel whose center sits exactly on the edge of the dot reads as
+/// half-covered rather than snapping hard to in-or-out.
+pub fn dot_coverage(px: (f32, f32), center: (f32, f32), radius: f32) -> f32 {
+    let dist = ((px.0 - center.0).powi(2) + (px.1 - center.1).powi(2)).sqrt();
+    (radius - dist + 0.5).clamp(0.0, 1.0)
+}
+
+/// Fractional coverage of an annular wedge -- the ring between
+/// `inner_radius` and `outer_radius`, swept from `start_angle` to
+/// `end_angle` -- sampled at pixel center `px`. Angles increase clockwise
+/// from the positive-x axis (3 o'clock), matching screen/canvas y-down
+/// coordinates and the angle convention [`crate::web::WebDisplays`] already
+/// used for its gradient arcs.
+///
+/// Radial and angular coverage are each softened the same way as
+/// [`dot_coverage`] and multiplied; this under-estimates coverage right at
+/// a wedge's corner (where both edges are soft at once) but is visually
+/// indistinguishable from an exact signed-area integral at the pixel
+/// densities this clock renders at.
+pub fn wedge_coverage(
+    px: (f32, f32),
+    center: (f32, f32),
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+) -> f32 {
+    use std::f32::consts::TAU;
+
+    let dx = px.0 - center.0;
+    let dy = px.1 - center.1;
+    let dist = (dx * dx + dy * dy).sqrt();
+    if dist <= f32::EPSILON {
+        return 0.0;
+    }
+
+    let outer_cov = (outer_radius - dist + 0.5).clamp(0.0, 1.0);
+    let inner_cov = (dist - inner_radius + 0.5).clamp(0.0, 1.0);
+    let radial_cov = outer_cov.min(inner_cov);
+    if radial_cov <= 0.0 {
+        return 0.0;
+    }
+
+    let mut angle = dy.atan2(dx).rem_euclid(TAU);
+    let span_start = start_angle.rem_euclid(TAU);
+    let mut span_end = end_angle.rem_euclid(TAU);
+    if span_end <= span_start {
+        span_end += TAU;
+    }
+    if angle < span_start {
+        angle += TAU;
+    }
+
+    // Arc length, in pixels, swept by one radian at this radius -- used to
+    // soften the two angular edges over roughly one pixel each.
+    let px_per_radian = dist;
+    let from_start = ((angle - span_start) * px_per_radian + 0.5).clamp(0.0, 1.0);
+    let from_end = ((span_end - angle) * px_per_radian + 0.5).clamp(0.0, 1.0);
+
+    radial_cov * from_start.min(from_end)
+}
+
+/// Composite `src` over `dst` with Porter-Duff source-over, treating `dst`
+/// as fully opaque (true of every background this clock draws over) and
+/// `src` as `coverage`-opaque: `out = src*cov + dst*(1-cov)`.
+pub fn blend_over(dst: Rgb888, src: Rgb888, coverage: f32) -> Rgb888 {
+    let coverage = coverage.clamp(0.0, 1.0);
+    let mix = |s: u8, d: u8| (s as f32 * coverage + d as f32 * (1.0 - coverage)).round() as u8;
+    Rgb888::new(
+        mix(src.r(), dst.r()),
+        mix(src.g(), dst.g()),
+        mix(src.b(), dst.b()),
+    )
+}
+
+/// A plain RGB framebuffer that the edge wedges and matrix dots are
+/// rasterized into before a backend hands the result to its own output --
+/// an `embedded_graphics_simulator::SimulatorDisplay`'s pixels, or a canvas
+/// `ImageData`.
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgb888>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32, background: Rgb888) -> Self {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn blend_pixel(&mut self, x: i64, y: i64, color: Rgb888, coverage: f32) {
+        if coverage <= 0.0 || x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let idx = (y as u32 * self.width + x as u32) as usize;
+        self.pixels[idx] = blend_over(self.pixels[idx], color, coverage);
+    }
+
+    /// Rasterize a round dot (e.g. one lit matrix LED) of `radius` pixels
+    /// centered at `center`, blending `color` over every pixel its bounding
+    /// box touches by [`dot_coverage`].
+    pub fn fill_dot(&mut self, center: (f32, f32), radius: f32, color: Rgb888) {
+        let r = radius.ceil() as i64 + 1;
+        let (cx, cy) = (center.0.round() as i64, center.1.round() as i64);
+        for y in (cy - r)..=(cy + r) {
+            for x in (cx - r)..=(cx + r) {
+                let sample = (x as f32 + 0.5, y as f32 + 0.5);
+                let cov = dot_coverage(sample, center, radius);
+                self.blend_pixel(x, y, color, cov);
+            }
+        }
+    }
+
+    /// Rasterize one edge-ring wedge (the angular slice one LED occupies),
+    /// blending `color` over every pixel its bounding box touches by
+    /// [`wedge_coverage`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_wedge(
+        &mut self,
+        center: (f32, f32),
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        color: Rgb888,
+    ) {
+        let r = outer_radius.ceil() as i64 + 1;
+        let (cx, cy) = (center.0.round() as i64, center.1.round() as i64);
+        for y in (cy - r)..=(cy + r) {
+            for x in (cx - r)..=(cx + r) {
+                let sample = (x as f32 + 0.5, y as f32 + 0.5);
+                let cov = wedge_coverage(
+                    sample,
+                    center,
+                    inner_radius,
+                    outer_radius,
+                    start_angle,
+                    end_angle,
+                );
+                self.blend_pixel(x, y, color, cov);
+            }
+        }
+    }
+
+    /// The composited pixels as `embedded_graphics` [`Pixel`]s, row-major
+    /// from the top-left, for backends (like [`crate::simulator::SimDisplays`])
+    /// that present through a `DrawTarget`.
+    pub fn into_pixels(self) -> impl Iterator<Item = Pixel<Rgb888>> {
+        let width = self.width;
+        self.pixels.into_iter().enumerate().map(move |(i, color)| {
+            let x = (i as u32 % width) as i32;
+            let y = (i as u32 / width) as i32;
+            Pixel(Point::new(x, y), color)
+        })
+    }
+
+    /// The composited image as tightly-packed RGBA8 rows (alpha always
+    /// opaque), the layout `web_sys::ImageData` expects, for backends (like
+    /// [`crate::web::WebDisplays`]) that present through a 2D canvas.
+    pub fn to_rgba_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.pixels.len() * 4);
+        for color in &self.pixels {
+            bytes.extend_from_slice(&[color.r(), color.g(), color.b(), 255]);
+        }
+        bytes
+    }
+}
@@ -24,7 +24,73 @@ V2: equation of time
 
 use std::f32::consts::PI;
 
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+    Utc,
+};
+
+/// Zenith angle (degrees) treated as the moment of sunrise/sunset, i.e. "full
+/// day": 90° plus atmospheric refraction and the sun's apparent radius.
+const FULL_DAY_ZENITH_DEG: f32 = 90.833;
+
+/// Zenith angle marking the start/end of civil twilight, 6° further below the
+/// horizon than [`FULL_DAY_ZENITH_DEG`].
+const CIVIL_TWILIGHT_ZENITH_DEG: f32 = FULL_DAY_ZENITH_DEG + 6.0;
+
+/// Equation of time (minutes) and solar declination (radians) for `date`, per
+/// the NOAA equations referenced in the module docs.
+fn solar_position(date: NaiveDate) -> (f32, f32) {
+    let yr = date.year();
+    let leap_year = yr % 4 == 0 && yr % 100 != 0;
+    let days = if leap_year { 366 } else { 365 };
+
+    // Fractional year in radians. We don't include a fractional day from the hour.
+    let ordinal_day = date.ordinal();
+    let gamma = (2.0 * PI) * (ordinal_day as f32) / (days as f32);
+
+    // equation of time, relating mean solar time and true solar time
+    let eqtime = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+
+    // solar declination angle (in radians):
+    let decl = 0.006918 - 0.399912 * (gamma).cos() + 0.070257 * (gamma).sin()
+        - 0.006758 * (2.0 * gamma).cos()
+        + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos()
+        + 0.00148 * (3.0 * gamma).sin();
+
+    (eqtime, decl)
+}
+
+/// Elevation of the sun above the horizon at `date_time`, in degrees
+/// (negative when below the horizon). Reuses the `gamma`/`eqtime`/`decl`
+/// computation shared with [`riseset`].
+pub fn solar_elevation<Tz: TimeZone>(date_time: &DateTime<Tz>, latitude: f32, longitude: f32) -> f32 {
+    let utc = date_time.with_timezone(&Utc);
+    let (eqtime, decl) = solar_position(utc.date_naive());
+
+    // Minutes since UTC midnight, corrected to true solar time.
+    let utc_minutes = utc.time().num_seconds_from_midnight() as f32 / 60.0;
+    let true_solar_minutes = (utc_minutes + 4.0 * longitude + eqtime).rem_euclid(1440.0);
+    let ha = (true_solar_minutes / 4.0 - 180.0).to_radians();
+
+    let lat = latitude.to_radians();
+    (lat.sin() * decl.sin() + lat.cos() * decl.cos() * ha.cos())
+        .asin()
+        .to_degrees()
+}
+
+/// Map a solar elevation (degrees) to a 0.0 (full night) - 1.0 (full day)
+/// blend fraction, linearly interpolating across the civil-twilight band and
+/// clamping outside it.
+pub fn twilight_fraction(elevation_deg: f32) -> f32 {
+    let night_elevation = 90.0 - CIVIL_TWILIGHT_ZENITH_DEG;
+    let day_elevation = 90.0 - FULL_DAY_ZENITH_DEG;
+    ((elevation_deg - night_elevation) / (day_elevation - night_elevation)).clamp(0.0, 1.0)
+}
 
 /// Compute the next year's rise and set times.
 #[cfg_attr(feature = "web", wasm_bindgen::prelude::wasm_bindgen)]
@@ -46,6 +112,54 @@ pub fn ephemerides(latitude: f64, longitude: f64) -> String {
     out
 }
 
+/// Compute the current temporal ("seasonal") hour at `now`, `latitude`,
+/// `longitude`. Daylight is divided into 12 equal hours from sunrise to
+/// sunset, and night into 12 equal hours from sunset to the following
+/// sunrise; when `now` falls in a night span crossing midnight, the
+/// neighboring day's rise/set is computed exactly (not approximated), so the
+/// division stays accurate even around the solstices when day length changes
+/// quickly.
+///
+/// Returns the hour number (1-12) and whether it's a daylight hour.
+pub fn temporal_hour<Tz: TimeZone>(now: &DateTime<Tz>, latitude: f32, longitude: f32) -> (u8, bool) {
+    let today = now.date_naive();
+    let (rise, _noon, set) = riseset(today, latitude, longitude, now.timezone());
+
+    let (start, end, is_day) = if *now >= rise && *now < set {
+        (rise, set, true)
+    } else if *now >= set {
+        let tomorrow = today.succ_opt().unwrap_or(today);
+        let (next_rise, _noon, _set) = riseset(tomorrow, latitude, longitude, now.timezone());
+        (set, next_rise, false)
+    } else {
+        let yesterday = today.pred_opt().unwrap_or(today);
+        let (_rise, _noon, prev_set) = riseset(yesterday, latitude, longitude, now.timezone());
+        (prev_set, rise, false)
+    };
+    let span = end.signed_duration_since(start.clone()).num_seconds().max(1);
+    let elapsed = now.signed_duration_since(start).num_seconds();
+    let idx = (elapsed as f64 / span as f64 * 12.0).floor() as i64;
+    let hour = idx.clamp(0, 11) as u8 + 1;
+    (hour, is_day)
+}
+
+/// Traditional Latin name for a daylight seasonal hour, for the four that
+/// land on canonical-hour quarter-points (1st/3rd/6th/9th); `None` for the
+/// other daylight hours and for all night hours, which have no equally
+/// well-known names.
+pub fn canonical_hour_name(hour: u8, is_day: bool) -> Option<&'static str> {
+    if !is_day {
+        return None;
+    }
+    match hour {
+        1 => Some("PRIME"),
+        3 => Some("TERCE"),
+        6 => Some("SEXT"),
+        9 => Some("NONE"),
+        _ => None,
+    }
+}
+
 /// Compute sun rise/noon/set times.
 pub fn riseset<T: TimeZone>(
     date: NaiveDate,
@@ -53,35 +167,14 @@ pub fn riseset<T: TimeZone>(
     longitude: f32,
     tz: T,
 ) -> (DateTime<T>, DateTime<T>, DateTime<T>) {
-    let yr = date.year();
     // The NOAA equations produce rise and set times in minutes past UTC midnight.
     // We'll complete the NOAA equations then convert back to DateTime.
     let (rise, snoon, set) = {
         // START OF NOAA EQUATIONS
-        let leap_year = yr % 4 == 0 && yr % 100 != 0;
-
-        let days = if leap_year { 366 } else { 365 };
-
-        // Fractional year in radians. We don't include a fractional day from the hour.
-        let ordinal_day = date.ordinal(); // - 1 + (date.hour() - 12) / 24;
-        let gamma = (2.0 * PI) * (ordinal_day as f32) / (days as f32);
-
-        // equation of time, relating mean solar time and true solar time
-        let eqtime = 229.18
-            * (0.000075 + 0.001868 * gamma.cos()
-                - 0.032077 * gamma.sin()
-                - 0.014615 * (2.0 * gamma).cos()
-                - 0.040849 * (2.0 * gamma).sin());
-
-        // solar declination angle (in radians):
-        let decl = 0.006918 - 0.399912 * (gamma).cos() + 0.070257 * (gamma).sin()
-            - 0.006758 * (2.0 * gamma).cos()
-            + 0.000907 * (2.0 * gamma).sin()
-            - 0.002697 * (3.0 * gamma).cos()
-            + 0.00148 * (3.0 * gamma).sin();
+        let (eqtime, decl) = solar_position(date);
 
         // The hour angle of the sunrise and sunset is:
-        let zenith: f32 = (90.833f32).to_radians();
+        let zenith: f32 = FULL_DAY_ZENITH_DEG.to_radians();
         let lat = latitude.to_radians();
 
         // We diverge from the PDF here and use the spreadsheet's form:
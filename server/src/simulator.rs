@@ -1,19 +1,33 @@
 use std::convert::Infallible;
 
 use embedded_graphics::{
-    draw_target::{DrawTarget, DrawTargetExt},
+    draw_target::DrawTarget,
     geometry::{OriginDimensions, Point, Size},
     pixelcolor::{Rgb888, RgbColor},
     primitives::Rectangle,
+    Pixel,
 };
 use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
 
-use crate::{drawing::render_edge, Displays, NeoPixelColor};
+use crate::{
+    drawing::{render_edge_aa, render_matrix_dots},
+    raster::Canvas,
+    Displays, NeoPixelColor,
+};
+
+/// Radius, in native simulator pixels, of a rasterized matrix LED dot.
+/// Slightly over half a pixel so adjacent lit LEDs blend into each other a
+/// touch at the edges rather than reading as hard-edged squares.
+const MATRIX_DOT_RADIUS: f32 = 0.6;
 
 pub struct SimDisplays {
     display: SimulatorDisplay<Rgb888>,
     window: Option<Window>,
     edge: Vec<NeoPixelColor>,
+    /// Matrix content for the current frame, buffered rather than drawn
+    /// straight to `display` so it can be rasterized as round LED dots
+    /// alongside the edge ring; see [`MatrixBuffer`].
+    matrix: Vec<Pixel<Rgb888>>,
 }
 
 impl SimDisplays {
@@ -42,13 +56,47 @@ impl SimDisplays {
             window: None,
             display,
             edge,
+            matrix: Vec::new(),
         }
     }
 
-    /// Flush to a screenshot instead of a display.
-    pub fn screenshot(&mut self) -> embedded_graphics_simulator::OutputImage<Rgb888> {
-        let settings = OutputSettingsBuilder::new().scale(20).build();
-        render_edge(&self.edge, &mut self.display);
+    /// Rasterize the current frame's edge wedges and matrix dots into a
+    /// fresh [`Canvas`] at `display`'s native resolution, shared by
+    /// [`Self::flush`] and [`Self::screenshot`].
+    fn composite(&mut self) -> Canvas {
+        let size = self.display.size();
+        let mut canvas = Canvas::new(size.width, size.height, Rgb888::BLACK);
+
+        let center = (size.width as f32 / 2.0, size.height as f32 / 2.0);
+        let outer_radius = center.0.min(center.1);
+        render_edge_aa(
+            &self.edge,
+            &mut canvas,
+            center,
+            outer_radius - 1.0,
+            outer_radius,
+        );
+
+        render_matrix_dots(
+            self.matrix.iter().copied(),
+            &mut canvas,
+            Point::new(2, 2),
+            1.0,
+            MATRIX_DOT_RADIUS,
+        );
+
+        canvas
+    }
+
+    /// Flush to a screenshot instead of a display, upscaled by `scale`
+    /// (pixels-per-LED-or-matrix-dot, same meaning as
+    /// `OutputSettingsBuilder::scale`).
+    pub fn screenshot(&mut self, scale: u32) -> embedded_graphics_simulator::OutputImage<Rgb888> {
+        let settings = OutputSettingsBuilder::new().scale(scale).build();
+        let canvas = self.composite();
+        self.display
+            .draw_iter(canvas.into_pixels())
+            .expect("infallible");
         let img = self.display.to_rgb_output_image(&settings);
         self.clear();
         img
@@ -70,6 +118,33 @@ impl Default for SimDisplays {
     }
 }
 
+/// A `DrawTarget` that buffers the matrix's pixels instead of drawing them
+/// straight to the display, so [`SimDisplays`] can rasterize them as round
+/// LED dots (via [`render_matrix_dots`]) once the frame is complete, the
+/// same way [`crate::web::WebDisplays`] always has.
+struct MatrixBuffer<'a> {
+    pixels: &'a mut Vec<Pixel<Rgb888>>,
+}
+
+impl OriginDimensions for MatrixBuffer<'_> {
+    fn size(&self) -> Size {
+        Size::new(32, 16)
+    }
+}
+
+impl DrawTarget for MatrixBuffer<'_> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.pixels.extend(pixels);
+        Ok(())
+    }
+}
+
 impl Displays for SimDisplays {
     fn edge(&mut self) -> &mut [NeoPixelColor] {
         &mut self.edge
@@ -79,18 +154,20 @@ impl Displays for SimDisplays {
         &mut self,
     ) -> impl embedded_graphics_core::draw_target::DrawTarget<Color = Rgb888, Error = Infallible>
     {
-        // Cropped translates; clipped ensures that OOB writes get dropped.
-        // But clipped borrows from cropped, so we can't chain them, alas.
-        self.display
-            .cropped(&Rectangle::new(Point::new(2, 2), Size::new(32, 16)))
+        self.matrix.clear();
+        MatrixBuffer {
+            pixels: &mut self.matrix,
+        }
     }
 
     fn flush(&mut self) -> Result<(), String> {
-        render_edge(&self.edge, &mut self.display);
+        let canvas = self.composite();
+        self.display
+            .draw_iter(canvas.into_pixels())
+            .expect("infallible");
         if let Some(window) = &mut self.window {
             window.update(&self.display);
         }
-        // self.clear();
         Ok(())
     }
 }
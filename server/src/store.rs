@@ -0,0 +1,177 @@
+//! Persistent storage for atmosphere samples, for trend views spanning more
+//! than the bounded in-memory window `Renderer` keeps for the aux sparklines.
+//!
+//! [`SqliteHistoryStore`] durably appends every sample to a `rusqlite`
+//! database; [`MemoryHistoryStore`] keeps the same bounded window in memory
+//! instead, for targets with no filesystem (namely WASM) or deployments that
+//! don't need the reading to survive a restart.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::atmosphere::AtmosphereSample;
+
+/// Which channel of an [`AtmosphereSample`] to pull from a [`HistoryStore`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Temperature,
+    Co2,
+    RelativeHumidity,
+}
+
+/// A downsampled window of one metric's recent history: at most one sample
+/// per matrix column, plus the min/max over the whole window so a trend view
+/// can autoscale to fill its vertical range.
+pub struct Trend {
+    pub samples: Vec<Option<f32>>,
+    pub min: f32,
+    pub max: f32,
+}
+
+/// A durable record of every atmosphere sample, queryable as a downsampled
+/// [`Trend`] for a scrolling trend view.
+pub trait HistoryStore {
+    /// Record a newly observed sample.
+    fn append(&mut self, sample: &AtmosphereSample);
+
+    /// The last `window` of `metric`, downsampled to at most `columns`
+    /// points (one per matrix column). `None` if there's nothing in the
+    /// window yet.
+    fn trend(&self, metric: Metric, window: Duration, columns: usize) -> Option<Trend>;
+}
+
+/// Downsample `values` (already in ascending timestamp order) to at most
+/// `columns` points, and compute their min/max.
+fn downsample(values: &[f32], columns: usize) -> Option<Trend> {
+    if values.is_empty() || columns == 0 {
+        return None;
+    }
+    let samples = (0..columns)
+        .map(|col| {
+            let idx = if columns <= 1 {
+                0
+            } else {
+                col * (values.len() - 1) / (columns - 1)
+            };
+            Some(values[idx])
+        })
+        .collect();
+    let min = values.iter().copied().fold(f32::MAX, f32::min);
+    let max = values.iter().copied().fold(f32::MIN, f32::max);
+    Some(Trend { samples, min, max })
+}
+
+/// In-memory [`HistoryStore`]: keeps only the most recent `capacity`
+/// samples, so memory use is bounded rather than growing forever. Usable on
+/// WASM (no filesystem) and anywhere the `sqlite` feature is off; a
+/// `Renderer` has no store until one is set with
+/// [`Renderer::set_history_store`](crate::Renderer::set_history_store).
+pub struct MemoryHistoryStore {
+    capacity: usize,
+    samples: VecDeque<AtmosphereSample>,
+}
+
+impl MemoryHistoryStore {
+    /// Keep up to `capacity` most-recent samples.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+}
+
+impl HistoryStore for MemoryHistoryStore {
+    fn append(&mut self, sample: &AtmosphereSample) {
+        if self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(*sample);
+    }
+
+    fn trend(&self, metric: Metric, window: Duration, columns: usize) -> Option<Trend> {
+        let since = Utc::now() - chrono::Duration::from_std(window).unwrap_or_default();
+        let values: Vec<f32> = self
+            .samples
+            .iter()
+            .filter(|s| s.timestamp >= since)
+            .filter_map(|s| match metric {
+                Metric::Temperature => s.temperature,
+                Metric::Co2 => s.co2_ppm,
+                Metric::RelativeHumidity => s.relative_humidity,
+            })
+            .collect();
+        downsample(&values, columns)
+    }
+}
+
+/// SQLite-backed [`HistoryStore`]: every sample is appended to an
+/// `atmosphere_samples` table, so the history survives a restart. Requires a
+/// filesystem, so it's gated behind the `sqlite` feature rather than being
+/// the default (WASM has none).
+#[cfg(feature = "sqlite")]
+pub struct SqliteHistoryStore {
+    conn: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteHistoryStore {
+    /// Open (creating if necessary) the database at `path` and ensure its
+    /// schema exists.
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = rusqlite::Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS atmosphere_samples (
+                timestamp INTEGER PRIMARY KEY,
+                temperature REAL,
+                relative_humidity REAL,
+                co2_ppm REAL
+            )",
+        )?;
+        Ok(Self { conn })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl HistoryStore for SqliteHistoryStore {
+    fn append(&mut self, sample: &AtmosphereSample) {
+        let result = self.conn.execute(
+            "INSERT OR REPLACE INTO atmosphere_samples
+                (timestamp, temperature, relative_humidity, co2_ppm)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                sample.timestamp.timestamp(),
+                sample.temperature,
+                sample.relative_humidity,
+                sample.co2_ppm,
+            ],
+        );
+        if let Err(e) = result {
+            tracing::warn!("failed to persist atmosphere sample: {e}");
+        }
+    }
+
+    fn trend(&self, metric: Metric, window: Duration, columns: usize) -> Option<Trend> {
+        let column = match metric {
+            Metric::Temperature => "temperature",
+            Metric::Co2 => "co2_ppm",
+            Metric::RelativeHumidity => "relative_humidity",
+        };
+        let since = (Utc::now() - chrono::Duration::from_std(window).unwrap_or_default()).timestamp();
+
+        let query = format!(
+            "SELECT {column} FROM atmosphere_samples
+             WHERE timestamp >= ?1 AND {column} IS NOT NULL
+             ORDER BY timestamp ASC"
+        );
+        let mut stmt = self.conn.prepare(&query).ok()?;
+        let values: Vec<f32> = stmt
+            .query_map(rusqlite::params![since], |row| row.get(0))
+            .ok()?
+            .filter_map(Result::ok)
+            .collect();
+        downsample(&values, columns)
+    }
+}
@@ -1,18 +1,21 @@
-use std::{convert::Infallible, f64::consts::PI, str::FromStr};
+use std::{convert::Infallible, str::FromStr};
 
 use chrono::{DateTime, FixedOffset, MappedLocalTime, NaiveDateTime, TimeDelta, TimeZone};
 use embedded_graphics::{
+    geometry::Point,
     pixelcolor::Rgb888,
     prelude::{DrawTarget, OriginDimensions, RgbColor, Size},
     Pixel,
 };
 /// Set up logging for the WASM simulator.
 use log::MakeConsoleWriter;
-use wasm_bindgen::prelude::*;
-use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlInputElement};
+use wasm_bindgen::{prelude::*, Clamped};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlInputElement, ImageData};
 
 use crate::{
     atmosphere::{AtmosphereSample, AtmosphereSampler},
+    drawing::{render_edge_aa, render_matrix_dots},
+    raster::Canvas,
     Displays, NeoPixelColor, Renderer, RendererSettings,
 };
 
@@ -193,9 +196,6 @@ impl WebDisplays {
         }
         let scale = scale as u32;
 
-        // TODO: Render the central matrix as dots (circles, with space).
-        // TODO: Render the edge as arcs.
-        // OK to emulate the SDL version for now.
         // 2 pixels on each edge; get the perimeter
         let perimeter = 60;
         let mut edge = Vec::new();
@@ -215,11 +215,6 @@ impl OriginDimensions for &mut WebDisplays {
     }
 }
 
-/// Get a fillStyle value for a given color.
-fn fill_color(color: Rgb888) -> String {
-    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
-}
-
 impl DrawTarget for &mut WebDisplays {
     type Color = Rgb888;
 
@@ -255,7 +250,7 @@ impl Displays for WebDisplays {
         let d = SIM_MATRIX * self.scale;
         self.canvas.set_width(d);
         self.canvas.set_height(d);
-        let center = d as f64 / 2.0;
+        let center = (d as f32 / 2.0, d as f32 / 2.0);
 
         let ctx = self
             .canvas
@@ -267,88 +262,36 @@ impl Displays for WebDisplays {
 
         ctx.clear_rect(0.0, 0.0, d as f64, d as f64);
 
-        let face_radius = ((SIM_MATRIX - 10) * self.scale) as f64 / 2.0;
-        {
-            let radius = d as f64 / 2.0;
-            // Draw the edge display:
-            let arc_size = 2.0 * PI / self.edge.len() as f64;
-            // Javascript by default measures arcs in clockwise radians? Eh?
-            const DOWN: f64 = PI / 2.0;
-            for (i, it) in self.edge.iter().enumerate() {
-                let start_angle = DOWN + (i as f64 * arc_size);
-                let end_angle = start_angle + arc_size;
-                let mid_angle = (start_angle + end_angle) / 2.0;
-
-                let (x_outer, y_outer) = (
-                    center + mid_angle.cos() * radius,
-                    center + mid_angle.sin() * radius,
-                );
-                let (x_inner, y_inner) = (
-                    center + mid_angle.cos() * face_radius,
-                    center + mid_angle.sin() * face_radius,
-                );
-
-                let [r, g, b, _w] = *it;
-                let gradient = ctx.create_linear_gradient(x_inner, y_inner, x_outer, y_outer);
-                gradient
-                    .add_color_stop(0.0, &fill_color(Rgb888::new(r, g, b)))
-                    .map_err(|e| format!("failed to stop gradient: {e:?}"))?;
-                // Fade to transparent, i.e. the background:
-                gradient
-                    .add_color_stop(1.0, &format!("rgba({r}, {g}, {b}, 0)"))
-                    .map_err(|e| format!("failed to stop gradient: {e:?}"))?;
-
-                // let fill = fill_color(Rgb888::WHITE);
-                // Begins a new path
-                ctx.begin_path();
-                ctx.move_to(center, center);
-                ctx.ellipse(center, center, radius, radius, 0.0, start_angle, end_angle)
-                    .map_err(|e| format!("could not draw edge arc: {e:?}"))?;
-                ctx.move_to(center, center);
-                ctx.close_path();
-                ctx.set_fill_style_canvas_gradient(&gradient);
-                ctx.fill();
-            }
-        }
-        // Draw an inner arc to mask off the face.
-        {
-            ctx.begin_path();
-            ctx.set_fill_style_str(&fill_color(Rgb888::BLACK));
-            ctx.ellipse(center, center, face_radius, face_radius, 0.0, 0.0, 2.0 * PI)
-                .map_err(|e| format!("could not draw center mask: {e:?}"))?;
-            ctx.close_path();
-            ctx.fill();
-        }
-        {
-            // Finally, draw each pixel in the matrix.
-            // We extend the matrix out to the full dimensions,
-            // and here compute the edges.
-            let matrix_offset_top = (SIM_MATRIX - DEFAULT_SIZE.height) / 2;
-            let matrix_offset_left = (SIM_MATRIX - DEFAULT_SIZE.width) / 2;
-            // Radius must be at least 1.
-            let r = std::cmp::max(self.scale / 4, 1) as f64;
-            // Since most colors will be the same, we only update the fill color if it changes.
-            let mut last_color = Rgb888::BLACK;
-            for Pixel(pt, color) in self.display.drain(0..) {
-                let (x, y) = (
-                    pt.x as u32 + matrix_offset_left,
-                    pt.y as u32 + matrix_offset_top,
-                );
-                let (x, y) = (x * self.scale, y * self.scale);
-                let (x, y) = (x as f64, y as f64);
-                ctx.begin_path();
-                ctx.move_to(x, y);
-                ctx.arc(x, y, r, 0.0, 2.0 * PI)
-                    .map_err(|e| format!("could not draw matrix pixel: {e:?}"))?;
-                ctx.close_path();
-
-                if color != last_color {
-                    ctx.set_fill_style_str(&fill_color(color));
-                    last_color = color;
-                }
-                ctx.fill();
-            }
-        }
+        // Rasterize the edge ring and the matrix dots into one shared
+        // framebuffer -- the same analytic coverage math SimDisplays uses
+        // -- then blit it onto the canvas in a single paint.
+        let face_radius = ((SIM_MATRIX - 10) * self.scale) as f32 / 2.0;
+        let outer_radius = d as f32 / 2.0;
+        let mut canvas = Canvas::new(d, d, Rgb888::BLACK);
+        render_edge_aa(&self.edge, &mut canvas, center, face_radius, outer_radius);
+
+        // We extend the matrix out to the full dimensions, and here compute
+        // the edges.
+        let matrix_offset_top = (SIM_MATRIX - DEFAULT_SIZE.height) / 2;
+        let matrix_offset_left = (SIM_MATRIX - DEFAULT_SIZE.width) / 2;
+        // Radius must be at least 1.
+        let dot_radius = (self.scale as f32 / 4.0).max(1.0);
+        render_matrix_dots(
+            self.display.drain(..),
+            &mut canvas,
+            Point::new(
+                (matrix_offset_left * self.scale) as i32,
+                (matrix_offset_top * self.scale) as i32,
+            ),
+            self.scale as f32,
+            dot_radius,
+        );
+
+        let bytes = canvas.to_rgba_bytes();
+        let image_data = ImageData::new_with_u8_clamped_array(Clamped(&bytes), d)
+            .map_err(|e| format!("could not build image data: {:?}", e))?;
+        ctx.put_image_data(&image_data, 0.0, 0.0)
+            .map_err(|e| format!("could not paint frame: {:?}", e))?;
 
         tracing::trace!("done drawing frame");
 